@@ -0,0 +1,3 @@
+use decomp::Decomp;
+
+common::criterion_benches!(Decomp);
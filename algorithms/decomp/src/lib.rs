@@ -1,9 +1,16 @@
-#![feature(test)]
+#![cfg_attr(feature = "nightly", feature(test))]
 
+#[cfg(feature = "nightly")]
 extern crate test;
 
-use common::{NUM_TILE_TYPE, ShantenCalculator, TileCount, TileCounts};
-use common::{shanten_benches, shanten_tests};
+use common::{
+    Block, Decomposition, DecompositionCalculator, NUM_TILE_TYPE, NecessaryTiles,
+    ShantenCalculator, TileCount, TileCounts, TileFlags,
+};
+use common::{
+    annotated_tests, decompositions_tests, discards_tests, necessary_tiles_tests, shanten_benches,
+    shanten_tests, single_suit_tests,
+};
 
 const MAX_NUM_BLOCKS: i8 = 4;
 const MAX_SHANTEN: i8 = 8;
@@ -106,7 +113,206 @@ fn cut_meld_cand(
     cut_meld_cand(hand, num_blocks, min_shanten, i + 1);
 }
 
-struct Decomp {}
+fn cut_meld_with_blocks<F>(
+    hand: &mut TileCounts,
+    num_blocks: &mut NumBlocks,
+    blocks: &mut Vec<Block>,
+    visit: &mut F,
+    i: usize,
+) where
+    F: FnMut(&TileCounts, &NumBlocks, &[Block]),
+{
+    if i >= NUM_TILE_TYPE {
+        cut_meld_cand_with_blocks(hand, num_blocks, blocks, visit, 0);
+        return;
+    }
+
+    // triplet
+    if hand[i] >= 3 {
+        num_blocks.num_meld += 1;
+        hand[i] -= 3;
+        blocks.push(Block::Triplet(i));
+        cut_meld_with_blocks(hand, num_blocks, blocks, visit, i);
+        blocks.pop();
+        hand[i] += 3;
+        num_blocks.num_meld -= 1;
+    }
+
+    // sequence
+    if is_suit(i) && i % 9 < 7 && hand[i] > 0 && hand[i + 1] > 0 && hand[i + 2] > 0 {
+        num_blocks.num_meld += 1;
+        hand[i] -= 1;
+        hand[i + 1] -= 1;
+        hand[i + 2] -= 1;
+        blocks.push(Block::Sequence(i));
+        cut_meld_with_blocks(hand, num_blocks, blocks, visit, i);
+        blocks.pop();
+        hand[i + 2] += 1;
+        hand[i + 1] += 1;
+        hand[i] += 1;
+        num_blocks.num_meld -= 1;
+    }
+
+    cut_meld_with_blocks(hand, num_blocks, blocks, visit, i + 1);
+}
+
+fn cut_meld_cand_with_blocks<F>(
+    hand: &mut TileCounts,
+    num_blocks: &mut NumBlocks,
+    blocks: &mut Vec<Block>,
+    visit: &mut F,
+    i: usize,
+) where
+    F: FnMut(&TileCounts, &NumBlocks, &[Block]),
+{
+    if i >= NUM_TILE_TYPE {
+        visit(hand, num_blocks, blocks);
+        return;
+    }
+
+    if num_blocks.get_num_blocks() < MAX_NUM_BLOCKS {
+        // pair (triplet candidate)
+        if hand[i] == 2 {
+            num_blocks.num_meld_cand += 1;
+            hand[i] -= 2;
+            blocks.push(Block::Pair(i));
+            cut_meld_cand_with_blocks(hand, num_blocks, blocks, visit, i);
+            blocks.pop();
+            hand[i] += 2;
+            num_blocks.num_meld_cand -= 1;
+        }
+
+        // edge joint or open joint
+        if is_suit(i) && i % 9 < 8 && hand[i] > 0 && hand[i + 1] > 0 {
+            num_blocks.num_meld_cand += 1;
+            hand[i] -= 1;
+            hand[i + 1] -= 1;
+            blocks.push(Block::Joint(i));
+            cut_meld_cand_with_blocks(hand, num_blocks, blocks, visit, i);
+            blocks.pop();
+            hand[i + 1] += 1;
+            hand[i] += 1;
+            num_blocks.num_meld_cand -= 1;
+        }
+
+        // middle joint
+        if is_suit(i) && i % 9 < 7 && hand[i] > 0 && hand[i + 2] > 0 {
+            num_blocks.num_meld_cand += 1;
+            hand[i] -= 1;
+            hand[i + 2] -= 1;
+            blocks.push(Block::MiddleJoint(i));
+            cut_meld_cand_with_blocks(hand, num_blocks, blocks, visit, i);
+            blocks.pop();
+            hand[i + 2] += 1;
+            hand[i] += 1;
+            num_blocks.num_meld_cand -= 1;
+        }
+    }
+
+    cut_meld_cand_with_blocks(hand, num_blocks, blocks, visit, i + 1);
+}
+
+/// Visits every decomposition of the hand with the pair, the extracted blocks and the remaining tiles.
+fn search_with_blocks<F>(hand: &TileCounts, mut visit: F)
+where
+    F: FnMut(&TileCounts, &NumBlocks, Option<usize>, &[Block]),
+{
+    let required_num_meld = (hand.iter().sum::<TileCount>() / 3) as i8;
+    let num_call = 4 - required_num_meld;
+    let mut num_blocks = NumBlocks {
+        num_meld: num_call,
+        num_meld_cand: 0,
+        num_pair: 0,
+    };
+    let mut hand_clone = *hand;
+    let mut blocks = Vec::with_capacity(MAX_NUM_BLOCKS as usize);
+
+    // Remove a possible pair and search with a pair
+    for i in 0..NUM_TILE_TYPE {
+        if hand_clone[i] >= 2 {
+            num_blocks.num_pair += 1;
+            hand_clone[i] -= 2;
+            cut_meld_with_blocks(
+                &mut hand_clone,
+                &mut num_blocks,
+                &mut blocks,
+                &mut |h: &TileCounts, n: &NumBlocks, b: &[Block]| visit(h, n, Some(i), b),
+                0,
+            );
+            hand_clone[i] += 2;
+            num_blocks.num_pair -= 1;
+        }
+    }
+
+    // Search without a pair
+    cut_meld_with_blocks(
+        &mut hand_clone,
+        &mut num_blocks,
+        &mut blocks,
+        &mut |h: &TileCounts, n: &NumBlocks, b: &[Block]| visit(h, n, None, b),
+        0,
+    );
+}
+
+fn meld_cand_waits(block: &Block) -> TileFlags {
+    match *block {
+        Block::Pair(i) => 1 << i,
+        Block::Joint(i) => {
+            let mut waits = 0;
+            if !i.is_multiple_of(9) {
+                waits |= 1 << (i - 1);
+            }
+            if i % 9 < 7 {
+                waits |= 1 << (i + 2);
+            }
+            waits
+        }
+        Block::MiddleJoint(i) => 1 << (i + 1),
+        Block::Triplet(_) | Block::Sequence(_) => 0,
+    }
+}
+
+fn add_necessary_tiles(
+    hand: &TileCounts,
+    num_blocks: &NumBlocks,
+    blocks: &[Block],
+    min_shanten: &mut i8,
+    tiles: &mut TileFlags,
+) {
+    let shanten = num_blocks.formula();
+    if shanten > *min_shanten {
+        return;
+    }
+    if shanten < *min_shanten {
+        *min_shanten = shanten;
+        *tiles = 0;
+    }
+
+    // Tiles that turn a meld candidate into a meld
+    for block in blocks {
+        *tiles |= meld_cand_waits(block);
+    }
+
+    // Tiles that turn an isolated tile into a pair or a meld candidate
+    let can_add_meld_cand = num_blocks.get_num_blocks() < MAX_NUM_BLOCKS;
+    for (i, &c) in hand.iter().enumerate() {
+        if c == 0 {
+            continue;
+        }
+        if num_blocks.num_pair == 0 || (can_add_meld_cand && c == 1) {
+            *tiles |= 1 << i;
+        }
+        if can_add_meld_cand && is_suit(i) {
+            let start = i - (i % 9).min(2);
+            let end = i + (8 - i % 9).min(2);
+            for j in start..=end {
+                *tiles |= 1 << j;
+            }
+        }
+    }
+}
+
+pub struct Decomp {}
 
 impl ShantenCalculator for Decomp {
     fn new() -> Self {
@@ -141,7 +347,46 @@ impl ShantenCalculator for Decomp {
 
         min_shanten
     }
+
+    fn calculate_necessary_tiles(&self, hand: &TileCounts) -> NecessaryTiles {
+        let mut min_shanten = MAX_SHANTEN;
+        let mut tiles = 0;
+
+        search_with_blocks(hand, |remaining, num_blocks, _, blocks| {
+            add_necessary_tiles(remaining, num_blocks, blocks, &mut min_shanten, &mut tiles);
+        });
+
+        NecessaryTiles::new(min_shanten, tiles, hand)
+    }
+}
+
+impl DecompositionCalculator for Decomp {
+    fn calculate_decompositions(&self, hand: &TileCounts) -> (i8, Vec<Decomposition>) {
+        let mut min_shanten = MAX_SHANTEN;
+        let mut decompositions = Vec::new();
+
+        search_with_blocks(hand, |remaining, num_blocks, pair, blocks| {
+            let shanten = num_blocks.formula();
+            if shanten > min_shanten {
+                return;
+            }
+            if shanten < min_shanten {
+                min_shanten = shanten;
+                decompositions.clear();
+            }
+            decompositions.push(Decomposition::new(pair, blocks, remaining));
+        });
+
+        decompositions.sort_unstable();
+        decompositions.dedup();
+        (min_shanten, decompositions)
+    }
 }
 
 shanten_tests!(Decomp);
+single_suit_tests!(Decomp, ignore);
+annotated_tests!(Decomp);
+decompositions_tests!(Decomp);
+necessary_tiles_tests!(Decomp);
+discards_tests!(Decomp);
 shanten_benches!(Decomp);
@@ -0,0 +1,3 @@
+use decomp_ara::DecompAra;
+
+common::criterion_benches!(DecompAra);
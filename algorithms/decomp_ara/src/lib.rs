@@ -1,9 +1,16 @@
-#![feature(test)]
+#![cfg_attr(feature = "nightly", feature(test))]
 
+#[cfg(feature = "nightly")]
 extern crate test;
 
-use common::{NUM_TILE_TYPE, ShantenCalculator, TileCount, TileCounts};
-use common::{shanten_benches, shanten_tests};
+use common::{
+    GroupCalculator, MAX_NUM_TILE, NUM_GROUP, NUM_TILE_TYPE, NecessaryTiles, ShantenCalculator,
+    TileCount, TileCounts,
+};
+use common::{
+    annotated_tests, discards_tests, incremental_tests, necessary_tiles_tests, shanten_benches,
+    shanten_tests, single_suit_tests,
+};
 
 const MAX_SHANTEN: i8 = 8;
 
@@ -24,11 +31,13 @@ fn formula(mut num_meld: i8, mut num_meld_cand: i8, has_pair: bool) -> i8 {
     MAX_SHANTEN - num_meld * 2 - num_meld_cand
 }
 
+#[derive(Clone, Copy)]
 struct NumBlocks {
     num_meld: i8,
     num_meld_cand: i8,
 }
 
+#[derive(Clone, Copy)]
 struct NumBlocksPattern {
     /// Pattern with the minimum number of isolated tiles
     a: NumBlocks,
@@ -185,11 +194,22 @@ fn count_honor_num_blocks(honor_hand: &[TileCount]) -> NumBlocks {
     }
 }
 
-fn calculate_shanten_impl(hand: &mut TileCounts, has_pair: bool, num_call: i8) -> i8 {
-    let num_blocks_m = count_suit_num_blocks(&mut hand[0..9], 0);
-    let num_blocks_p = count_suit_num_blocks(&mut hand[9..18], 0);
-    let num_blocks_s = count_suit_num_blocks(&mut hand[18..27], 0);
-    let z = count_honor_num_blocks(&hand[27..34]);
+fn count_group_num_blocks(hand: &mut TileCounts, group: usize) -> NumBlocksPattern {
+    if group < 3 {
+        count_suit_num_blocks(&mut hand[group * 9..group * 9 + 9], 0)
+    } else {
+        let z = count_honor_num_blocks(&hand[27..34]);
+        NumBlocksPattern { a: z, b: z }
+    }
+}
+
+fn calculate_shanten_from_num_blocks(
+    num_blocks: &[NumBlocksPattern; 4],
+    has_pair: bool,
+    num_call: i8,
+) -> i8 {
+    let [num_blocks_m, num_blocks_p, num_blocks_s, num_blocks_z] = num_blocks;
+    let z = &num_blocks_z.a;
 
     let mut min = MAX_SHANTEN;
 
@@ -208,7 +228,20 @@ fn calculate_shanten_impl(hand: &mut TileCounts, has_pair: bool, num_call: i8) -
     min
 }
 
-struct DecompAra {}
+fn calculate_shanten_impl(hand: &mut TileCounts, has_pair: bool, num_call: i8) -> i8 {
+    let num_blocks = std::array::from_fn(|group| count_group_num_blocks(hand, group));
+    calculate_shanten_from_num_blocks(&num_blocks, has_pair, num_call)
+}
+
+/// Blocks of a group of tiles, cached by [IncrementalCalculator](common::IncrementalCalculator).
+#[derive(Clone)]
+pub struct GroupNumBlocks {
+    without_pair: NumBlocksPattern,
+    /// Blocks after removing a pair of each tile type in the group
+    with_pair: [Option<NumBlocksPattern>; 9],
+}
+
+pub struct DecompAra {}
 
 impl ShantenCalculator for DecompAra {
     fn new() -> Self {
@@ -235,7 +268,132 @@ impl ShantenCalculator for DecompAra {
 
         min
     }
+
+    fn calculate_necessary_tiles(&self, hand: &TileCounts) -> NecessaryTiles {
+        let required_num_meld = (hand.iter().sum::<TileCount>() / 3) as i8;
+        let num_call = 4 - required_num_meld;
+        let mut hand_clone = *hand;
+
+        // Count the blocks of each group only once.
+        // A draw changes the blocks of a single group, so the other groups are reused.
+        let num_blocks: [NumBlocksPattern; 4] =
+            std::array::from_fn(|group| count_group_num_blocks(&mut hand_clone, group));
+        let num_blocks_without_pair: [Option<NumBlocksPattern>; NUM_TILE_TYPE] =
+            std::array::from_fn(|i| {
+                (hand_clone[i] >= 2).then(|| {
+                    hand_clone[i] -= 2;
+                    let r = count_group_num_blocks(&mut hand_clone, i / 9);
+                    hand_clone[i] += 2;
+                    r
+                })
+            });
+
+        let calculate_shanten_with_draw = |hand: &mut TileCounts, drawn: usize| {
+            let group = drawn / 9;
+            let mut num_blocks_drawn = num_blocks;
+            num_blocks_drawn[group] = count_group_num_blocks(hand, group);
+
+            // Calculate the shanten number without a pair
+            let mut min = calculate_shanten_from_num_blocks(&num_blocks_drawn, false, num_call);
+
+            // Remove a possible pair and calculate the shanten number with a pair
+            for i in 0..NUM_TILE_TYPE {
+                if hand[i] >= 2 {
+                    let mut num_blocks_with_pair = num_blocks_drawn;
+                    if i / 9 == group {
+                        hand[i] -= 2;
+                        num_blocks_with_pair[group] = count_group_num_blocks(hand, group);
+                        hand[i] += 2;
+                    } else {
+                        num_blocks_with_pair[i / 9] = num_blocks_without_pair[i].unwrap();
+                    }
+                    let temp =
+                        calculate_shanten_from_num_blocks(&num_blocks_with_pair, true, num_call);
+                    min = min.min(temp);
+                }
+            }
+
+            min
+        };
+
+        // Calculate the shanten number of the hand itself
+        let mut shanten = calculate_shanten_from_num_blocks(&num_blocks, false, num_call);
+        for i in 0..NUM_TILE_TYPE {
+            if let Some(r) = num_blocks_without_pair[i] {
+                let mut num_blocks_with_pair = num_blocks;
+                num_blocks_with_pair[i / 9] = r;
+                let temp = calculate_shanten_from_num_blocks(&num_blocks_with_pair, true, num_call);
+                shanten = shanten.min(temp);
+            }
+        }
+
+        let mut tiles = 0;
+        for i in 0..NUM_TILE_TYPE {
+            if hand_clone[i] as usize >= MAX_NUM_TILE {
+                continue;
+            }
+
+            hand_clone[i] += 1;
+            if calculate_shanten_with_draw(&mut hand_clone, i) < shanten {
+                tiles |= 1 << i;
+            }
+            hand_clone[i] -= 1;
+        }
+
+        NecessaryTiles::new(shanten, tiles, hand)
+    }
+}
+
+impl GroupCalculator for DecompAra {
+    type GroupResult = GroupNumBlocks;
+
+    fn calculate_group(&self, hand: &TileCounts, group: usize) -> GroupNumBlocks {
+        let mut hand_clone = *hand;
+        let without_pair = count_group_num_blocks(&mut hand_clone, group);
+        let with_pair = std::array::from_fn(|n| {
+            let i = group * 9 + n;
+            (i < NUM_TILE_TYPE && hand_clone[i] >= 2).then(|| {
+                hand_clone[i] -= 2;
+                let r = count_group_num_blocks(&mut hand_clone, group);
+                hand_clone[i] += 2;
+                r
+            })
+        });
+        GroupNumBlocks {
+            without_pair,
+            with_pair,
+        }
+    }
+
+    fn calculate_shanten_from_groups(
+        &self,
+        groups: &[GroupNumBlocks; NUM_GROUP],
+        num_tiles: u8,
+    ) -> i8 {
+        let num_call = 4 - (num_tiles / 3) as i8;
+        let num_blocks = groups.clone().map(|g| g.without_pair);
+
+        // Calculate the shanten number without a pair
+        let mut min = calculate_shanten_from_num_blocks(&num_blocks, false, num_call);
+
+        // Replace the blocks of a single group with those after removing a pair
+        for (group, g) in groups.iter().enumerate() {
+            for r in g.with_pair.iter().flatten() {
+                let mut num_blocks_with_pair = num_blocks;
+                num_blocks_with_pair[group] = *r;
+                let temp = calculate_shanten_from_num_blocks(&num_blocks_with_pair, true, num_call);
+                min = min.min(temp);
+            }
+        }
+
+        min
+    }
 }
 
 shanten_tests!(DecompAra);
+single_suit_tests!(DecompAra, ignore);
+annotated_tests!(DecompAra);
+necessary_tiles_tests!(DecompAra);
+discards_tests!(DecompAra);
+incremental_tests!(DecompAra);
 shanten_benches!(DecompAra);
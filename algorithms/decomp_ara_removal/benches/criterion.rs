@@ -0,0 +1,3 @@
+use decomp_ara_removal::DecompAraRemoval;
+
+common::criterion_benches!(DecompAraRemoval);
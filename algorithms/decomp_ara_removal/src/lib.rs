@@ -1,9 +1,10 @@
-#![feature(test)]
+#![cfg_attr(feature = "nightly", feature(test))]
 
+#[cfg(feature = "nightly")]
 extern crate test;
 
 use common::{NUM_TILE_TYPE, ShantenCalculator, TileCount, TileCounts};
-use common::{shanten_benches, shanten_tests};
+use common::{annotated_tests, discards_tests, shanten_benches, shanten_tests, single_suit_tests};
 
 const MAX_SHANTEN: i8 = 8;
 
@@ -241,7 +242,7 @@ fn calculate_shanten_impl(hand: &mut TileCounts, has_pair: bool, num_call: i8) -
     min
 }
 
-struct DecompAraRemoval {}
+pub struct DecompAraRemoval {}
 
 impl ShantenCalculator for DecompAraRemoval {
     fn new() -> Self {
@@ -271,4 +272,7 @@ impl ShantenCalculator for DecompAraRemoval {
 }
 
 shanten_tests!(DecompAraRemoval);
+single_suit_tests!(DecompAraRemoval, ignore);
+annotated_tests!(DecompAraRemoval);
+discards_tests!(DecompAraRemoval);
 shanten_benches!(DecompAraRemoval);
@@ -0,0 +1,3 @@
+use decomp_fixed_pruned::DecompFixedPruned;
+
+common::criterion_benches!(DecompFixedPruned);
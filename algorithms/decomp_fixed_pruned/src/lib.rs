@@ -1,9 +1,16 @@
-#![feature(test)]
+#![cfg_attr(feature = "nightly", feature(test))]
 
+#[cfg(feature = "nightly")]
 extern crate test;
 
-use common::{NUM_TILE_TYPE, ShantenCalculator, TileCount, TileCounts};
-use common::{shanten_benches, shanten_tests};
+use common::{
+    Block, Decomposition, DecompositionCalculator, NUM_TILE_TYPE, ShantenCalculator, TileCount,
+    TileCounts,
+};
+use common::{
+    annotated_tests, decompositions_tests, discards_tests, shanten_benches, shanten_tests,
+    single_suit_tests,
+};
 
 const MAX_NUM_BLOCKS: i8 = 4;
 const MAX_SHANTEN: i8 = 8;
@@ -98,33 +105,9 @@ fn cut_meld_cand(
     }
 
     if i >= NUM_TILE_TYPE {
-        if (num_blocks.num_meld == 4 && num_blocks.num_meld_cand == 0 && num_blocks.num_pair == 0)
-            || (num_blocks.num_meld == 3
-                && num_blocks.num_meld_cand == 1
-                && num_blocks.num_pair == 0)
-        {
-            // lack of isolated tiles pattern 1: isolated tile for pair
-            cut_isolated_tile_for_pair(hand, original, num_blocks, min_shanten);
-            return;
-        } else if num_blocks.num_meld == 3
-            && num_blocks.num_meld_cand == 0
-            && num_blocks.num_pair == 1
-        {
-            // lack of isolated tiles pattern 2: isolated tile for meld
-            cut_isolated_tile_for_meld(hand, original, num_blocks, min_shanten);
-            return;
-        } else if num_blocks.num_meld == 3
-            && num_blocks.num_meld_cand == 0
-            && num_blocks.num_pair == 0
-        {
-            // lack of isolated tiles pattern 3: isolated tile 1 for pair, isolated tile 2 for meld
-            cut_isolated_tile_for_pair_and_meld(hand, original, num_blocks, min_shanten);
-            return;
-        } else {
-            // enough isolated tiles
-            *min_shanten = *min_shanten.min(&mut num_blocks.formula());
-            return;
-        }
+        *min_shanten =
+            *min_shanten.min(&mut formula_with_isolated_tiles(hand, original, num_blocks));
+        return;
     }
 
     if num_blocks.get_num_blocks() < MAX_NUM_BLOCKS {
@@ -195,48 +178,228 @@ fn cut_meld_cand(
     );
 }
 
+struct DecompositionSearch {
+    blocks: Vec<Block>,
+    decompositions: Vec<Decomposition>,
+    min_shanten: i8,
+}
+
+fn cut_meld_for_decompositions(
+    hand: &mut TileCounts,
+    original: &TileCounts,
+    num_blocks: &mut NumBlocks,
+    search: &mut DecompositionSearch,
+    pair_index: usize,
+    i: usize,
+) {
+    if i >= NUM_TILE_TYPE {
+        let lower_bound = num_blocks.calculate_lower_bound();
+        cut_meld_cand_for_decompositions(
+            hand,
+            original,
+            num_blocks,
+            search,
+            pair_index,
+            0,
+            lower_bound,
+        );
+        return;
+    }
+
+    // triplet
+    if hand[i] >= 3 {
+        num_blocks.num_meld += 1;
+        hand[i] -= 3;
+        search.blocks.push(Block::Triplet(i));
+        cut_meld_for_decompositions(hand, original, num_blocks, search, pair_index, i);
+        search.blocks.pop();
+        hand[i] += 3;
+        num_blocks.num_meld -= 1;
+    }
+
+    // sequence
+    if is_suit(i) && i % 9 < 7 && hand[i] > 0 && hand[i + 1] > 0 && hand[i + 2] > 0 {
+        num_blocks.num_meld += 1;
+        hand[i] -= 1;
+        hand[i + 1] -= 1;
+        hand[i + 2] -= 1;
+        search.blocks.push(Block::Sequence(i));
+        cut_meld_for_decompositions(hand, original, num_blocks, search, pair_index, i);
+        search.blocks.pop();
+        hand[i + 2] += 1;
+        hand[i + 1] += 1;
+        hand[i] += 1;
+        num_blocks.num_meld -= 1;
+    }
+
+    cut_meld_for_decompositions(hand, original, num_blocks, search, pair_index, i + 1);
+}
+
+fn cut_meld_cand_for_decompositions(
+    hand: &mut TileCounts,
+    original: &TileCounts,
+    num_blocks: &mut NumBlocks,
+    search: &mut DecompositionSearch,
+    pair_index: usize,
+    i: usize,
+    lower_bound: i8,
+) {
+    // pruning (decompositions as good as the current minimum are still needed)
+    if search.min_shanten < lower_bound {
+        return;
+    }
+
+    if i >= NUM_TILE_TYPE {
+        let shanten = formula_with_isolated_tiles(hand, original, num_blocks);
+        if shanten > search.min_shanten {
+            return;
+        }
+        if shanten < search.min_shanten {
+            search.min_shanten = shanten;
+            search.decompositions.clear();
+        }
+        let pair = (pair_index < NUM_TILE_TYPE).then_some(pair_index);
+        search
+            .decompositions
+            .push(Decomposition::new(pair, &search.blocks, hand));
+        return;
+    }
+
+    if num_blocks.get_num_blocks() < MAX_NUM_BLOCKS {
+        // pair (triplet candidate)
+        if hand[i] == 2 && i != pair_index {
+            num_blocks.num_meld_cand += 1;
+            hand[i] -= 2;
+            search.blocks.push(Block::Pair(i));
+            cut_meld_cand_for_decompositions(
+                hand,
+                original,
+                num_blocks,
+                search,
+                pair_index,
+                i,
+                lower_bound,
+            );
+            search.blocks.pop();
+            hand[i] += 2;
+            num_blocks.num_meld_cand -= 1;
+        }
+
+        // edge joint or open joint
+        if is_suit(i) && i % 9 < 8 && hand[i] > 0 && hand[i + 1] > 0 {
+            num_blocks.num_meld_cand += 1;
+            hand[i] -= 1;
+            hand[i + 1] -= 1;
+            search.blocks.push(Block::Joint(i));
+            cut_meld_cand_for_decompositions(
+                hand,
+                original,
+                num_blocks,
+                search,
+                pair_index,
+                i,
+                lower_bound,
+            );
+            search.blocks.pop();
+            hand[i + 1] += 1;
+            hand[i] += 1;
+            num_blocks.num_meld_cand -= 1;
+        }
+
+        // middle joint
+        if is_suit(i) && i % 9 < 7 && hand[i] > 0 && hand[i + 2] > 0 {
+            num_blocks.num_meld_cand += 1;
+            hand[i] -= 1;
+            hand[i + 2] -= 1;
+            search.blocks.push(Block::MiddleJoint(i));
+            cut_meld_cand_for_decompositions(
+                hand,
+                original,
+                num_blocks,
+                search,
+                pair_index,
+                i,
+                lower_bound,
+            );
+            search.blocks.pop();
+            hand[i + 2] += 1;
+            hand[i] += 1;
+            num_blocks.num_meld_cand -= 1;
+        }
+    }
+
+    cut_meld_cand_for_decompositions(
+        hand,
+        original,
+        num_blocks,
+        search,
+        pair_index,
+        i + 1,
+        lower_bound,
+    );
+}
+
+fn formula_with_isolated_tiles(
+    hand: &TileCounts,
+    original: &TileCounts,
+    num_blocks: &NumBlocks,
+) -> i8 {
+    if (num_blocks.num_meld == 4 && num_blocks.num_meld_cand == 0 && num_blocks.num_pair == 0)
+        || (num_blocks.num_meld == 3 && num_blocks.num_meld_cand == 1 && num_blocks.num_pair == 0)
+    {
+        // lack of isolated tiles pattern 1: isolated tile for pair
+        cut_isolated_tile_for_pair(hand, original, num_blocks)
+    } else if num_blocks.num_meld == 3 && num_blocks.num_meld_cand == 0 && num_blocks.num_pair == 1
+    {
+        // lack of isolated tiles pattern 2: isolated tile for meld
+        cut_isolated_tile_for_meld(hand, original, num_blocks)
+    } else if num_blocks.num_meld == 3 && num_blocks.num_meld_cand == 0 && num_blocks.num_pair == 0
+    {
+        // lack of isolated tiles pattern 3: isolated tile 1 for pair, isolated tile 2 for meld
+        cut_isolated_tile_for_pair_and_meld(hand, original, num_blocks)
+    } else {
+        // enough isolated tiles
+        num_blocks.formula()
+    }
+}
+
 fn cut_isolated_tile_for_pair(
     hand: &TileCounts,
     original: &TileCounts,
     num_blocks: &NumBlocks,
-    min_shanten: &mut i8,
-) {
+) -> i8 {
     for i in 0..NUM_TILE_TYPE {
         if hand[i] > 0 && original[i] < 3 {
             // enough isolated tiles
-            *min_shanten = *min_shanten.min(&mut num_blocks.formula());
-            return;
+            return num_blocks.formula();
         }
     }
 
     // lack of isolated tiles
-    *min_shanten = *min_shanten.min(&mut (num_blocks.formula() + 1));
+    num_blocks.formula() + 1
 }
 
 fn cut_isolated_tile_for_meld(
     hand: &TileCounts,
     original: &TileCounts,
     num_blocks: &NumBlocks,
-    min_shanten: &mut i8,
-) {
+) -> i8 {
     for i in 0..NUM_TILE_TYPE {
         if (is_suit(i) && hand[i] > 0) || (is_honor(i) && hand[i] > 0 && original[i] < 3) {
             // enough isolated tiles
-            *min_shanten = *min_shanten.min(&mut num_blocks.formula());
-            return;
+            return num_blocks.formula();
         }
     }
 
     // lack of isolated tiles
-    *min_shanten = *min_shanten.min(&mut (num_blocks.formula() + 1));
+    num_blocks.formula() + 1
 }
 
 fn cut_isolated_tile_for_pair_and_meld(
     hand: &TileCounts,
     original: &TileCounts,
     num_blocks: &NumBlocks,
-    min_shanten: &mut i8,
-) {
+) -> i8 {
     let mut count = 0i8;
 
     for i in 0..NUM_TILE_TYPE {
@@ -245,10 +408,10 @@ fn cut_isolated_tile_for_pair_and_meld(
         }
     }
 
-    *min_shanten = *min_shanten.min(&mut (num_blocks.formula() + if count >= 2 { 0 } else { 1 }));
+    num_blocks.formula() + if count >= 2 { 0 } else { 1 }
 }
 
-struct DecompFixedPruned {}
+pub struct DecompFixedPruned {}
 
 impl ShantenCalculator for DecompFixedPruned {
     fn new() -> Self {
@@ -299,5 +462,59 @@ impl ShantenCalculator for DecompFixedPruned {
     }
 }
 
+impl DecompositionCalculator for DecompFixedPruned {
+    fn calculate_decompositions(&self, hand: &TileCounts) -> (i8, Vec<Decomposition>) {
+        let required_num_meld = (hand.iter().sum::<TileCount>() / 3) as i8;
+        let num_call = 4 - required_num_meld;
+        let mut num_blocks = NumBlocks {
+            num_meld: num_call,
+            num_meld_cand: 0,
+            num_pair: 0,
+        };
+        let mut hand_clone = *hand;
+        let mut search = DecompositionSearch {
+            blocks: Vec::with_capacity(MAX_NUM_BLOCKS as usize),
+            decompositions: Vec::new(),
+            min_shanten: MAX_SHANTEN,
+        };
+
+        // Remove a possible pair and collect the decompositions with a pair
+        for i in 0..NUM_TILE_TYPE {
+            if hand_clone[i] >= 2 {
+                num_blocks.num_pair += 1;
+                hand_clone[i] -= 2;
+                cut_meld_for_decompositions(
+                    &mut hand_clone,
+                    hand,
+                    &mut num_blocks,
+                    &mut search,
+                    i,
+                    0,
+                );
+                hand_clone[i] += 2;
+                num_blocks.num_pair -= 1;
+            }
+        }
+
+        // Collect the decompositions without a pair
+        cut_meld_for_decompositions(
+            &mut hand_clone,
+            hand,
+            &mut num_blocks,
+            &mut search,
+            NUM_TILE_TYPE,
+            0,
+        );
+
+        search.decompositions.sort_unstable();
+        search.decompositions.dedup();
+        (search.min_shanten, search.decompositions)
+    }
+}
+
 shanten_tests!(DecompFixedPruned);
+single_suit_tests!(DecompFixedPruned);
+annotated_tests!(DecompFixedPruned);
+decompositions_tests!(DecompFixedPruned);
+discards_tests!(DecompFixedPruned);
 shanten_benches!(DecompFixedPruned);
@@ -0,0 +1,3 @@
+use decomp_kobayashi::DecompKobayashi;
+
+common::criterion_benches!(DecompKobayashi);
@@ -0,0 +1,3 @@
+use decomp_pruned::DecompPruned;
+
+common::criterion_benches!(DecompPruned);
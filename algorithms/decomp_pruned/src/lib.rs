@@ -1,9 +1,13 @@
-#![feature(test)]
+#![cfg_attr(feature = "nightly", feature(test))]
 
+#[cfg(feature = "nightly")]
 extern crate test;
 
-use common::{NUM_TILE_TYPE, ShantenCalculator, TileCount, TileCounts};
-use common::{shanten_benches, shanten_tests};
+use common::{NUM_TILE_TYPE, NecessaryTiles, ShantenCalculator, TileCount, TileCounts, TileFlags};
+use common::{
+    annotated_tests, discards_tests, necessary_tiles_tests, shanten_benches, shanten_tests,
+    single_suit_tests,
+};
 
 const MAX_NUM_BLOCKS: i8 = 4;
 const MAX_SHANTEN: i8 = 8;
@@ -117,7 +121,194 @@ fn cut_meld_cand(
     cut_meld_cand(hand, num_blocks, min_shanten, i + 1, lower_bound);
 }
 
-struct DecompPruned {}
+fn cut_meld_for_necessary_tiles(
+    hand: &mut TileCounts,
+    num_blocks: &mut NumBlocks,
+    meld_cand_waits: &mut Vec<TileFlags>,
+    min_shanten: &mut i8,
+    tiles: &mut TileFlags,
+    i: usize,
+) {
+    if i >= NUM_TILE_TYPE {
+        let lower_bound = num_blocks.calculate_lower_bound();
+        cut_meld_cand_for_necessary_tiles(
+            hand,
+            num_blocks,
+            meld_cand_waits,
+            min_shanten,
+            tiles,
+            0,
+            lower_bound,
+        );
+        return;
+    }
+
+    // triplet
+    if hand[i] >= 3 {
+        num_blocks.num_meld += 1;
+        hand[i] -= 3;
+        cut_meld_for_necessary_tiles(hand, num_blocks, meld_cand_waits, min_shanten, tiles, i);
+        hand[i] += 3;
+        num_blocks.num_meld -= 1;
+    }
+
+    // sequence
+    if is_suit(i) && i % 9 < 7 && hand[i] > 0 && hand[i + 1] > 0 && hand[i + 2] > 0 {
+        num_blocks.num_meld += 1;
+        hand[i] -= 1;
+        hand[i + 1] -= 1;
+        hand[i + 2] -= 1;
+        cut_meld_for_necessary_tiles(hand, num_blocks, meld_cand_waits, min_shanten, tiles, i);
+        hand[i + 2] += 1;
+        hand[i + 1] += 1;
+        hand[i] += 1;
+        num_blocks.num_meld -= 1;
+    }
+
+    cut_meld_for_necessary_tiles(hand, num_blocks, meld_cand_waits, min_shanten, tiles, i + 1);
+}
+
+fn cut_meld_cand_for_necessary_tiles(
+    hand: &mut TileCounts,
+    num_blocks: &mut NumBlocks,
+    meld_cand_waits: &mut Vec<TileFlags>,
+    min_shanten: &mut i8,
+    tiles: &mut TileFlags,
+    i: usize,
+    lower_bound: i8,
+) {
+    // pruning (decompositions as good as the current minimum are still needed)
+    if *min_shanten < lower_bound {
+        return;
+    }
+
+    if i >= NUM_TILE_TYPE {
+        add_necessary_tiles(hand, num_blocks, meld_cand_waits, min_shanten, tiles);
+        return;
+    }
+
+    if num_blocks.get_num_blocks() < MAX_NUM_BLOCKS {
+        // pair (triplet candidate)
+        if hand[i] == 2 {
+            num_blocks.num_meld_cand += 1;
+            hand[i] -= 2;
+            meld_cand_waits.push(1 << i);
+            cut_meld_cand_for_necessary_tiles(
+                hand,
+                num_blocks,
+                meld_cand_waits,
+                min_shanten,
+                tiles,
+                i,
+                lower_bound,
+            );
+            meld_cand_waits.pop();
+            hand[i] += 2;
+            num_blocks.num_meld_cand -= 1;
+        }
+
+        // edge joint or open joint
+        if is_suit(i) && i % 9 < 8 && hand[i] > 0 && hand[i + 1] > 0 {
+            let mut waits = 0;
+            if !i.is_multiple_of(9) {
+                waits |= 1 << (i - 1);
+            }
+            if i % 9 < 7 {
+                waits |= 1 << (i + 2);
+            }
+            num_blocks.num_meld_cand += 1;
+            hand[i] -= 1;
+            hand[i + 1] -= 1;
+            meld_cand_waits.push(waits);
+            cut_meld_cand_for_necessary_tiles(
+                hand,
+                num_blocks,
+                meld_cand_waits,
+                min_shanten,
+                tiles,
+                i,
+                lower_bound,
+            );
+            meld_cand_waits.pop();
+            hand[i + 1] += 1;
+            hand[i] += 1;
+            num_blocks.num_meld_cand -= 1;
+        }
+
+        // middle joint
+        if is_suit(i) && i % 9 < 7 && hand[i] > 0 && hand[i + 2] > 0 {
+            num_blocks.num_meld_cand += 1;
+            hand[i] -= 1;
+            hand[i + 2] -= 1;
+            meld_cand_waits.push(1 << (i + 1));
+            cut_meld_cand_for_necessary_tiles(
+                hand,
+                num_blocks,
+                meld_cand_waits,
+                min_shanten,
+                tiles,
+                i,
+                lower_bound,
+            );
+            meld_cand_waits.pop();
+            hand[i + 2] += 1;
+            hand[i] += 1;
+            num_blocks.num_meld_cand -= 1;
+        }
+    }
+
+    cut_meld_cand_for_necessary_tiles(
+        hand,
+        num_blocks,
+        meld_cand_waits,
+        min_shanten,
+        tiles,
+        i + 1,
+        lower_bound,
+    );
+}
+
+fn add_necessary_tiles(
+    hand: &TileCounts,
+    num_blocks: &NumBlocks,
+    meld_cand_waits: &[TileFlags],
+    min_shanten: &mut i8,
+    tiles: &mut TileFlags,
+) {
+    let shanten = num_blocks.formula();
+    if shanten > *min_shanten {
+        return;
+    }
+    if shanten < *min_shanten {
+        *min_shanten = shanten;
+        *tiles = 0;
+    }
+
+    // Tiles that turn a meld candidate into a meld
+    for waits in meld_cand_waits {
+        *tiles |= waits;
+    }
+
+    // Tiles that turn an isolated tile into a pair or a meld candidate
+    let can_add_meld_cand = num_blocks.get_num_blocks() < MAX_NUM_BLOCKS;
+    for (i, &c) in hand.iter().enumerate() {
+        if c == 0 {
+            continue;
+        }
+        if num_blocks.num_pair == 0 || (can_add_meld_cand && c == 1) {
+            *tiles |= 1 << i;
+        }
+        if can_add_meld_cand && is_suit(i) {
+            let start = i - (i % 9).min(2);
+            let end = i + (8 - i % 9).min(2);
+            for j in start..=end {
+                *tiles |= 1 << j;
+            }
+        }
+    }
+}
+
+pub struct DecompPruned {}
 
 impl ShantenCalculator for DecompPruned {
     fn new() -> Self {
@@ -152,7 +343,56 @@ impl ShantenCalculator for DecompPruned {
 
         min_shanten
     }
+
+    fn calculate_necessary_tiles(&self, hand: &TileCounts) -> NecessaryTiles {
+        let required_num_meld = (hand.iter().sum::<TileCount>() / 3) as i8;
+        let num_call = 4 - required_num_meld;
+        let mut num_blocks = NumBlocks {
+            num_meld: num_call,
+            num_meld_cand: 0,
+            num_pair: 0,
+        };
+        let mut hand_clone = *hand;
+        let mut meld_cand_waits = Vec::with_capacity(MAX_NUM_BLOCKS as usize);
+
+        let mut min_shanten = MAX_SHANTEN;
+        let mut tiles = 0;
+
+        // Remove a possible pair and collect the necessary tiles with a pair
+        for i in 0..NUM_TILE_TYPE {
+            if hand_clone[i] >= 2 {
+                num_blocks.num_pair += 1;
+                hand_clone[i] -= 2;
+                cut_meld_for_necessary_tiles(
+                    &mut hand_clone,
+                    &mut num_blocks,
+                    &mut meld_cand_waits,
+                    &mut min_shanten,
+                    &mut tiles,
+                    0,
+                );
+                hand_clone[i] += 2;
+                num_blocks.num_pair -= 1;
+            }
+        }
+
+        // Collect the necessary tiles without a pair
+        cut_meld_for_necessary_tiles(
+            &mut hand_clone,
+            &mut num_blocks,
+            &mut meld_cand_waits,
+            &mut min_shanten,
+            &mut tiles,
+            0,
+        );
+
+        NecessaryTiles::new(min_shanten, tiles, hand)
+    }
 }
 
 shanten_tests!(DecompPruned);
+single_suit_tests!(DecompPruned, ignore);
+annotated_tests!(DecompPruned);
+necessary_tiles_tests!(DecompPruned);
+discards_tests!(DecompPruned);
 shanten_benches!(DecompPruned);
@@ -0,0 +1,3 @@
+use definition::Definition;
+
+common::criterion_benches!(Definition);
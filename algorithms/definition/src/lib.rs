@@ -0,0 +1,131 @@
+#![cfg_attr(feature = "nightly", feature(test))]
+
+#[cfg(feature = "nightly")]
+extern crate test;
+
+use common::{MAX_HAND_SIZE, MAX_NUM_TILE, NUM_TILE_TYPE, ShantenCalculator, TileCounts};
+use common::{annotated_tests, shanten_benches, shanten_tests, single_suit_tests};
+
+/// Number of meld types: triplets (0-33) and sequences (34-54)
+const NUM_MELD_TYPE: usize = NUM_TILE_TYPE + 21;
+
+fn meld_tiles(meld: usize) -> [usize; 3] {
+    if meld < NUM_TILE_TYPE {
+        // triplet
+        [meld; 3]
+    } else {
+        // sequence
+        let suit = (meld - NUM_TILE_TYPE) / 7;
+        let i = suit * 9 + (meld - NUM_TILE_TYPE) % 7;
+        [i, i + 1, i + 2]
+    }
+}
+
+pub struct Definition {}
+
+struct Search<'a> {
+    hand: &'a TileCounts,
+    /// Tiles of the winning hand chosen so far
+    target: TileCounts,
+    /// Number of tiles of the hand kept in `target`
+    num_kept: u8,
+    max_kept: u8,
+}
+
+impl Search<'_> {
+    /// Adds a tile to the winning hand and returns `true` if it is kept from the hand.
+    fn add(&mut self, i: usize) -> bool {
+        self.target[i] += 1;
+        let is_kept = self.target[i] <= self.hand[i];
+        if is_kept {
+            self.num_kept += 1;
+        }
+        is_kept
+    }
+
+    fn remove(&mut self, i: usize) {
+        if self.target[i] <= self.hand[i] {
+            self.num_kept -= 1;
+        }
+        self.target[i] -= 1;
+    }
+
+    fn search_pair(&mut self) {
+        self.max_kept = self.max_kept.max(self.num_kept);
+
+        for i in 0..NUM_TILE_TYPE {
+            if self.target[i] as usize + 2 > MAX_NUM_TILE {
+                continue;
+            }
+            let is_kept = self.add(i);
+            self.add(i);
+            // A pair that keeps no tiles is no better than a pair of tiles not in the hand
+            if is_kept {
+                self.max_kept = self.max_kept.max(self.num_kept);
+            }
+            self.remove(i);
+            self.remove(i);
+        }
+    }
+
+    fn search_melds(&mut self, start: usize, num_meld: usize) {
+        // Even if every remaining tile is kept, the maximum cannot be exceeded
+        if self.num_kept as usize + num_meld * 3 + 2 <= self.max_kept as usize {
+            return;
+        }
+
+        self.search_pair();
+        if num_meld == 0 {
+            return;
+        }
+
+        for meld in start..NUM_MELD_TYPE {
+            let tiles = meld_tiles(meld);
+            if tiles
+                .iter()
+                .any(|&i| self.target[i] as usize >= MAX_NUM_TILE)
+                || (meld < NUM_TILE_TYPE && self.target[meld] as usize + 3 > MAX_NUM_TILE)
+            {
+                continue;
+            }
+
+            let num_kept = self.num_kept;
+            tiles.iter().for_each(|&i| _ = self.add(i));
+            // A meld that keeps no tiles is no better than a meld of tiles not in the hand
+            if self.num_kept > num_kept {
+                self.search_melds(meld, num_meld - 1);
+            }
+            tiles.iter().for_each(|&i| self.remove(i));
+        }
+    }
+}
+
+impl ShantenCalculator for Definition {
+    fn new() -> Self {
+        Definition {}
+    }
+
+    fn calculate_shanten(&self, hand: &TileCounts) -> i8 {
+        let sum: u8 = hand.iter().sum();
+        debug_assert!(sum as usize <= MAX_HAND_SIZE, "too many tiles: {sum}");
+        let num_call = 4u8.saturating_sub(sum / 3);
+        let num_meld = (4 - num_call) as usize;
+
+        let mut search = Search {
+            hand,
+            target: [0; NUM_TILE_TYPE],
+            num_kept: 0,
+            max_kept: 0,
+        };
+        search.search_melds(0, num_meld);
+
+        // Every tile of the winning hand that is not kept must be exchanged
+        let num_exchange = num_meld as i8 * 3 + 2 - search.max_kept as i8;
+        num_exchange - 1
+    }
+}
+
+shanten_tests!(Definition);
+single_suit_tests!(Definition);
+annotated_tests!(Definition);
+shanten_benches!(Definition);
@@ -0,0 +1,3 @@
+use dp_min_plus::DpMinPlus;
+
+common::criterion_benches!(DpMinPlus);
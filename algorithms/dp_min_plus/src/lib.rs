@@ -0,0 +1,126 @@
+#![cfg_attr(feature = "nightly", feature(test))]
+
+#[cfg(feature = "nightly")]
+extern crate test;
+
+use common::{MAX_HAND_SIZE, MAX_NUM_TILE, ShantenCalculator, TileCounts};
+use common::{annotated_tests, shanten_benches, shanten_tests, single_suit_tests};
+
+const MAX_NUM_MELD: usize = 4;
+const MAX_NUM_PAIR: usize = 1;
+const INF: u8 = u8::MAX;
+
+/// Minimum number of tiles to draw for each number of melds (0-4) and pairs (0-1).
+type Costs = [[u8; MAX_NUM_PAIR + 1]; MAX_NUM_MELD + 1];
+
+/// Number of DP states: (melds) * (pairs) * (sequences started 1 rank before)
+/// * (sequences started 2 ranks before)
+const NUM_STATE: usize =
+    (MAX_NUM_MELD + 1) * (MAX_NUM_PAIR + 1) * (MAX_NUM_MELD + 1) * (MAX_NUM_MELD + 1);
+
+const fn state_index(num_meld: usize, num_pair: usize, s1: usize, s2: usize) -> usize {
+    ((num_meld * (MAX_NUM_PAIR + 1) + num_pair) * (MAX_NUM_MELD + 1) + s1) * (MAX_NUM_MELD + 1) + s2
+}
+
+/// Calculates the costs of a suit or the honors by DP over the ranks.
+///
+/// At each rank, the triplet, the pair and the sequences starting at the rank are chosen.
+/// Tiles of the rank required by them but not in the hand must be drawn.
+fn calculate_group_costs(counts: &[u8], is_suit: bool) -> Costs {
+    let mut dp = [INF; NUM_STATE];
+    dp[state_index(0, 0, 0, 0)] = 0;
+
+    for (rank, &c) in counts.iter().enumerate() {
+        // Sequences start at 1-7 of a suit
+        let max_num_sequence = if is_suit && rank + 2 < counts.len() {
+            MAX_NUM_MELD
+        } else {
+            0
+        };
+        let mut next = [INF; NUM_STATE];
+
+        for m in 0..=MAX_NUM_MELD {
+            for p in 0..=MAX_NUM_PAIR {
+                // Sequences in progress are already counted in the melds
+                for s1 in 0..=m {
+                    for s2 in 0..=m - s1 {
+                        let cost = dp[state_index(m, p, s1, s2)];
+                        if cost == INF {
+                            continue;
+                        }
+
+                        for triplet in 0..=(MAX_NUM_MELD - m).min(1) {
+                            for pair in 0..=MAX_NUM_PAIR - p {
+                                let required = triplet * 3 + pair * 2 + s1 + s2;
+                                if required > MAX_NUM_TILE {
+                                    continue;
+                                }
+
+                                let max_num_sequence = max_num_sequence
+                                    .min(MAX_NUM_MELD - m - triplet)
+                                    .min(MAX_NUM_TILE - required);
+                                for sequence in 0..=max_num_sequence {
+                                    let cost =
+                                        cost + ((required + sequence) as u8).saturating_sub(c);
+                                    let k =
+                                        state_index(m + triplet + sequence, p + pair, sequence, s1);
+                                    next[k] = next[k].min(cost);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        dp = next;
+    }
+
+    // Every sequence has ended at the last rank
+    std::array::from_fn(|m| std::array::from_fn(|p| dp[state_index(m, p, 0, 0)]))
+}
+
+/// Combines the costs of two groups of tiles by min-plus convolution.
+fn combine(a: &Costs, b: &Costs) -> Costs {
+    let mut result = [[INF; MAX_NUM_PAIR + 1]; MAX_NUM_MELD + 1];
+    for m1 in 0..=MAX_NUM_MELD {
+        for m2 in 0..=MAX_NUM_MELD - m1 {
+            for p1 in 0..=MAX_NUM_PAIR {
+                for p2 in 0..=MAX_NUM_PAIR - p1 {
+                    let cost = a[m1][p1].saturating_add(b[m2][p2]);
+                    result[m1 + m2][p1 + p2] = result[m1 + m2][p1 + p2].min(cost);
+                }
+            }
+        }
+    }
+    result
+}
+
+pub struct DpMinPlus {}
+
+impl ShantenCalculator for DpMinPlus {
+    fn new() -> Self {
+        DpMinPlus {}
+    }
+
+    fn calculate_shanten(&self, hand: &TileCounts) -> i8 {
+        let sum: u8 = hand.iter().sum();
+        debug_assert!(sum as usize <= MAX_HAND_SIZE, "too many tiles: {sum}");
+        let num_call = 4u8.saturating_sub(sum / 3);
+        let num_meld = (4 - num_call) as usize;
+
+        let costs = [0, 9, 18]
+            .map(|base| calculate_group_costs(&hand[base..base + 9], true))
+            .iter()
+            .fold(calculate_group_costs(&hand[27..], false), |costs, suit| {
+                combine(&costs, suit)
+            });
+
+        costs[num_meld][1] as i8 - 1
+    }
+}
+
+shanten_tests!(DpMinPlus);
+single_suit_tests!(DpMinPlus);
+annotated_tests!(DpMinPlus);
+shanten_benches!(DpMinPlus);
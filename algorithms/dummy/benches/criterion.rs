@@ -0,0 +1,3 @@
+use dummy::Dummy;
+
+common::criterion_benches!(Dummy);
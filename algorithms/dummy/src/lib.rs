@@ -1,11 +1,12 @@
-#![feature(test)]
+#![cfg_attr(feature = "nightly", feature(test))]
 
+#[cfg(feature = "nightly")]
 extern crate test;
 
 use common::{ShantenCalculator, TileCounts};
-use common::{shanten_benches, shanten_tests};
+use common::{shanten_benches, shanten_tests, single_suit_tests};
 
-struct Dummy {}
+pub struct Dummy {}
 
 impl ShantenCalculator for Dummy {
     fn new() -> Self {
@@ -20,6 +21,7 @@ impl ShantenCalculator for Dummy {
 
 // Generate test cases using macro
 shanten_tests!(Dummy);
+single_suit_tests!(Dummy, ignore);
 
 // Generate benchmarks using macro
 shanten_benches!(Dummy);
@@ -0,0 +1,4 @@
+use decomp_fixed_pruned::DecompFixedPruned;
+use overall::Overall;
+
+common::criterion_benches!(Overall<DecompFixedPruned>);
@@ -0,0 +1,218 @@
+#![cfg_attr(feature = "nightly", feature(test))]
+
+#[cfg(feature = "nightly")]
+extern crate test;
+
+use common::{Hand, MAX_HAND_SIZE, ShantenCalculator, TileCounts};
+use common::{annotated_tests, shanten_benches};
+use seven_pairs::SevenPairs;
+use thirteen_orphans::ThirteenOrphans;
+
+/// Hand form for which a shanten number is calculated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Form {
+    /// General form (n melds and 1 pair)
+    General,
+    /// Seven Pairs (Chiitoitsu)
+    SevenPairs,
+    /// Thirteen Orphans (Kokushi Musou)
+    ThirteenOrphans,
+}
+
+/// Calculates the overall shanten number across the general form, Seven Pairs and Thirteen Orphans.
+///
+/// The general form is calculated by `C`.
+/// Seven Pairs and Thirteen Orphans are only taken into account for closed hands of 13 or 14 tiles.
+pub struct Overall<C: ShantenCalculator> {
+    general: C,
+    seven_pairs: SevenPairs,
+    thirteen_orphans: ThirteenOrphans,
+}
+
+impl<C: ShantenCalculator> Overall<C> {
+    /// Calculates the overall shanten number and the forms that achieve it.
+    ///
+    /// # Arguments
+    ///
+    /// * `hand` - Reference to a [TileCounts] struct representing the hand's tile counts
+    ///
+    /// # Returns
+    ///
+    /// The shanten number and the [Form]s that achieve it, in the order of the variants.
+    pub fn calculate_shanten_with_forms(&self, hand: &TileCounts) -> (i8, Vec<Form>) {
+        let sum: u8 = hand.iter().sum();
+        debug_assert!(sum as usize <= MAX_HAND_SIZE, "too many tiles: {sum}");
+        let num_call = 4u8.saturating_sub(sum / 3);
+        let general = self.general.calculate_shanten(hand);
+        self.calculate_shanten_impl(hand, general, num_call == 0)
+    }
+
+    fn calculate_shanten_impl(
+        &self,
+        hand: &TileCounts,
+        general: i8,
+        has_no_meld: bool,
+    ) -> (i8, Vec<Form>) {
+        let mut shantens = vec![(Form::General, general)];
+
+        if has_no_meld {
+            shantens.push((Form::SevenPairs, self.seven_pairs.calculate_shanten(hand)));
+            shantens.push((
+                Form::ThirteenOrphans,
+                self.thirteen_orphans.calculate_shanten(hand),
+            ));
+        }
+
+        let min_shanten = shantens.iter().map(|&(_, s)| s).min().unwrap();
+        let forms = shantens
+            .into_iter()
+            .filter(|&(_, s)| s == min_shanten)
+            .map(|(f, _)| f)
+            .collect();
+
+        (min_shanten, forms)
+    }
+}
+
+impl<C: ShantenCalculator> ShantenCalculator for Overall<C> {
+    fn new() -> Self {
+        Overall {
+            general: C::new(),
+            seven_pairs: SevenPairs::new(),
+            thirteen_orphans: ThirteenOrphans::new(),
+        }
+    }
+
+    fn calculate_shanten(&self, hand: &TileCounts) -> i8 {
+        self.calculate_shanten_with_forms(hand).0
+    }
+
+    fn calculate_shanten_with_melds(&self, hand: &Hand) -> i8 {
+        // The general form is delegated so that a meld-aware override of `C` is used
+        let general = self.general.calculate_shanten_with_melds(hand);
+        // Special forms are not possible with any meld, including a closed kan
+        self.calculate_shanten_impl(hand.concealed(), general, hand.melds().is_empty())
+            .0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::{Meld, TileCountsExt};
+    use decomp_fixed_pruned::DecompFixedPruned;
+
+    fn calculate(code: &str) -> (i8, Vec<Form>) {
+        let calculator = Overall::<DecompFixedPruned>::new();
+        calculator.calculate_shanten_with_forms(&TileCounts::from_code(code))
+    }
+
+    #[test]
+    fn test_general_form() {
+        assert_eq!(calculate("123m456p789s11222z"), (-1, vec![Form::General]));
+    }
+
+    #[test]
+    fn test_seven_pairs() {
+        assert_eq!(
+            calculate("1133m5577p99s1122z"),
+            (-1, vec![Form::SevenPairs])
+        );
+    }
+
+    #[test]
+    fn test_thirteen_orphans() {
+        assert_eq!(
+            calculate("19m19p19s1234567z"),
+            (0, vec![Form::ThirteenOrphans])
+        );
+    }
+
+    #[test]
+    fn test_multiple_forms() {
+        assert_eq!(
+            calculate("112233m445566p77z"),
+            (-1, vec![Form::General, Form::SevenPairs])
+        );
+        assert_eq!(
+            calculate("112233m445566p7z"),
+            (0, vec![Form::General, Form::SevenPairs])
+        );
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "too many tiles")]
+    fn test_too_many_tiles() {
+        calculate("123456789m123456p");
+    }
+
+    #[test]
+    fn test_special_forms_excluded_with_calls() {
+        // Thirteen Orphans would be 3-shanten, but the hand has a call
+        let hand = TileCounts::from_code("19m19p19s1234z");
+        let general = DecompFixedPruned::new().calculate_shanten(&hand);
+        assert!(general > 3);
+        assert_eq!(calculate("19m19p19s1234z"), (general, vec![Form::General]));
+    }
+
+    #[test]
+    fn test_special_forms_excluded_with_closed_kan() {
+        let calculator = Overall::<DecompFixedPruned>::new();
+        let hand = Hand::new(
+            TileCounts::from_code("19m19p19s1234z"),
+            vec![Meld::ClosedKan(33)],
+        )
+        .unwrap();
+        let general = DecompFixedPruned::new().calculate_shanten(hand.concealed());
+        assert!(general > 3);
+        assert_eq!(calculator.calculate_shanten_with_melds(&hand), general);
+    }
+
+    #[test]
+    fn test_kan_tiles_not_used_elsewhere() {
+        // With the kan declared, 1m cannot complete 123m, so the hand is tenpai waiting on 4m
+        let calculator = Overall::<DecompFixedPruned>::new();
+        let hand = Hand::new(
+            TileCounts::from_code("23m456p789s11z"),
+            vec![Meld::ClosedKan(0)],
+        )
+        .unwrap();
+        assert_eq!(calculator.calculate_shanten_with_melds(&hand), 0);
+
+        // Concealed, the same tiles form 111m123m456p789s11z
+        let concealed = TileCounts::from_code("111123m456p789s11z");
+        assert_eq!(calculator.calculate_shanten(&concealed), -1);
+    }
+
+    /// Calculator that only reports a winning hand through `calculate_shanten_with_melds`.
+    struct MeldAware {}
+
+    impl ShantenCalculator for MeldAware {
+        fn new() -> Self {
+            MeldAware {}
+        }
+
+        fn calculate_shanten(&self, _hand: &TileCounts) -> i8 {
+            8
+        }
+
+        fn calculate_shanten_with_melds(&self, _hand: &Hand) -> i8 {
+            -1
+        }
+    }
+
+    #[test]
+    fn test_melds_delegated_to_general_form() {
+        let calculator = Overall::<MeldAware>::new();
+        let hand = Hand::new(
+            TileCounts::from_code("456p789s11z"),
+            vec![Meld::Chi(0), Meld::Pon(33)],
+        )
+        .unwrap();
+        assert_eq!(calculator.calculate_shanten_with_melds(&hand), -1);
+    }
+}
+
+annotated_tests!(Overall<decomp_fixed_pruned::DecompFixedPruned>, overall);
+shanten_benches!(Overall<decomp_fixed_pruned::DecompFixedPruned>);
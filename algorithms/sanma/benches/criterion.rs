@@ -0,0 +1,4 @@
+use decomp_fixed_pruned::DecompFixedPruned;
+use sanma::Sanma;
+
+common::criterion_benches!(Sanma<DecompFixedPruned>, ["sanma"]);
@@ -0,0 +1,193 @@
+#![cfg_attr(feature = "nightly", feature(test))]
+
+#[cfg(feature = "nightly")]
+extern crate test;
+
+use common::sanma_benches;
+use common::{
+    HandError, MAX_NUM_TILE, NUM_TILE_TYPE, NecessaryTiles, ShantenCalculator, TileCounts, TileSet,
+    ValidatedTileCounts,
+};
+
+/// Manzu tiles used in three-player mahjong (1m and 9m).
+const SANMA_MANZU_TILES: [usize; 2] = [0, 8];
+
+/// Tile types that never form a sequence in three-player mahjong (1m, 9m and 1z–7z).
+const HONOR_LIKE_TILES: [usize; 9] = [0, 8, 27, 28, 29, 30, 31, 32, 33];
+
+/// Moves the honor-like tiles of a sanma hand to the honor group.
+///
+/// The honor group has only 7 slots. If 8 or 9 honor-like tile types are in the hand,
+/// the remaining ones are put back at 1m and 9m. Without 2m–8m in the hand,
+/// a tile type with 3 copies or fewer at 1m or 9m can only be completed by its own copies,
+/// as an honor tile, so only tile types with all 4 copies need a slot in the honor group.
+fn to_standard_hand(hand: &TileCounts) -> TileCounts {
+    let mut honor_like: Vec<_> = HONOR_LIKE_TILES
+        .iter()
+        .map(|&i| hand[i])
+        .filter(|&c| c > 0)
+        .collect();
+    // Tile types with 4 copies come first
+    honor_like.sort_by_key(|&c| c as usize != MAX_NUM_TILE);
+
+    let mut standard = *hand;
+    for i in HONOR_LIKE_TILES {
+        standard[i] = 0;
+    }
+    let slots = (27..NUM_TILE_TYPE).chain(SANMA_MANZU_TILES);
+    for (i, c) in slots.zip(honor_like) {
+        standard[i] = c;
+    }
+    standard
+}
+
+/// Calculates the shanten number of a three-player mahjong (sanma) hand.
+///
+/// The general form is calculated by `C` after moving 1m and 9m to the honor group,
+/// since they cannot form sequences without 2m–8m.
+/// The hand must not hold 2m–8m.
+pub struct Sanma<C: ShantenCalculator> {
+    calculator: C,
+}
+
+impl<C: ShantenCalculator> ShantenCalculator for Sanma<C> {
+    fn new() -> Self {
+        Sanma {
+            calculator: C::new(),
+        }
+    }
+
+    fn calculate_shanten(&self, hand: &TileCounts) -> i8 {
+        self.calculator.calculate_shanten(&to_standard_hand(hand))
+    }
+
+    fn try_calculate_shanten(&self, hand: &TileCounts) -> Result<i8, HandError> {
+        let hand = ValidatedTileCounts::new_in(*hand, TileSet::Sanma)?;
+        Ok(self.calculate_shanten(hand.tile_counts()))
+    }
+
+    fn calculate_necessary_tiles(&self, hand: &TileCounts) -> NecessaryTiles {
+        let shanten = self.calculate_shanten(hand);
+        let mut hand_clone = *hand;
+        let mut tiles = 0;
+
+        // 2m–8m can never be drawn
+        for i in (0..NUM_TILE_TYPE).filter(|&i| TileSet::Sanma.contains(i)) {
+            if hand_clone[i] as usize >= MAX_NUM_TILE {
+                continue;
+            }
+
+            hand_clone[i] += 1;
+            if self.calculate_shanten(&hand_clone) < shanten {
+                tiles |= 1 << i;
+            }
+            hand_clone[i] -= 1;
+        }
+
+        NecessaryTiles::new(shanten, tiles, hand)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::TileCountsExt;
+    use decomp_fixed_pruned::DecompFixedPruned;
+
+    fn calculate(code: &str) -> i8 {
+        let calculator = Sanma::<DecompFixedPruned>::new();
+        calculator.calculate_shanten(&TileCounts::from_code(code))
+    }
+
+    #[test]
+    fn test_to_standard_hand() {
+        let hand = TileCounts::from_code("19m123p11s1234567z");
+        assert_eq!(
+            to_standard_hand(&hand),
+            TileCounts::from_code("19m123p11s1234567z")
+        );
+
+        // 9999m takes a slot in the honor group, and two single honors are put back at 1m and 9m
+        let hand = TileCounts::from_code("1119999m1234567z");
+        assert_eq!(
+            to_standard_hand(&hand),
+            TileCounts::from_code("19m111122234567z")
+        );
+    }
+
+    #[test]
+    fn test_shanten_win() {
+        assert_eq!(calculate("111999m123p789s11z"), -1);
+    }
+
+    #[test]
+    fn test_shanten_waiting_for_the_5th_tile() {
+        // Both 1111m and 9999m have no copies left to draw, as honor tiles
+        let expected =
+            DecompFixedPruned::new().calculate_shanten(&TileCounts::from_code("11112222z11s"));
+        assert_eq!(calculate("11119999m11s"), expected);
+        assert!(
+            DecompFixedPruned::new().calculate_shanten(&TileCounts::from_code("11119999m11s"))
+                < expected
+        );
+    }
+
+    #[test]
+    fn test_shanten_nine_honor_like_tiles() {
+        assert_eq!(calculate("19m19p1234567z11s"), 7);
+    }
+
+    #[test]
+    fn test_try_shanten() {
+        let calculator = Sanma::<DecompFixedPruned>::new();
+        assert_eq!(
+            calculator.try_calculate_shanten(&TileCounts::from_code("111999m123p789s11z")),
+            Ok(-1)
+        );
+        assert_eq!(
+            calculator.try_calculate_shanten(&TileCounts::from_code("123m456p789s11222z")),
+            Err(HandError::NotInTileSet(1))
+        );
+    }
+
+    #[test]
+    fn test_necessary_tiles() {
+        let calculator = Sanma::<DecompFixedPruned>::new();
+        let necessary_tiles =
+            calculator.calculate_necessary_tiles(&TileCounts::from_code("1m123456p789s111z"));
+        assert_eq!(necessary_tiles.shanten, 0);
+        assert_eq!(necessary_tiles.tiles, 1 << 0);
+        assert_eq!(necessary_tiles.num_tiles, 3);
+    }
+
+    /// Checks that 1m and 9m with 3 copies or fewer give the same shanten number
+    /// in the manzu group and in the honor group, which [to_standard_hand] relies on.
+    #[test]
+    fn test_manzu_terminals_as_honors() {
+        let calculator = DecompFixedPruned::new();
+        let hands = common::load_hands(common::resource_path("hands_sanma_10000.txt")).unwrap();
+
+        let mut num_checked = 0;
+        for hand in hands {
+            let num_honor_like = HONOR_LIKE_TILES.iter().filter(|&&i| hand[i] > 0).count();
+            if num_honor_like > 7
+                || SANMA_MANZU_TILES
+                    .iter()
+                    .any(|&i| hand[i] as usize == MAX_NUM_TILE)
+            {
+                continue;
+            }
+
+            assert_eq!(
+                calculator.calculate_shanten(&hand),
+                calculator.calculate_shanten(&to_standard_hand(&hand)),
+                "hand: {}",
+                common::to_code(&hand)
+            );
+            num_checked += 1;
+        }
+        assert!(num_checked > 0);
+    }
+}
+
+sanma_benches!(Sanma<decomp_fixed_pruned::DecompFixedPruned>);
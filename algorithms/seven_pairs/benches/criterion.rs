@@ -0,0 +1,3 @@
+use seven_pairs::SevenPairs;
+
+common::criterion_benches!(SevenPairs);
@@ -0,0 +1,43 @@
+#![cfg_attr(feature = "nightly", feature(test))]
+
+#[cfg(feature = "nightly")]
+extern crate test;
+
+use common::{ShantenCalculator, TileCounts};
+use common::{seven_pairs_tests, shanten_benches};
+
+const MAX_SHANTEN: i8 = 13;
+const NUM_PAIRS: i8 = 7;
+
+pub struct SevenPairs {}
+
+impl ShantenCalculator for SevenPairs {
+    fn new() -> Self {
+        SevenPairs {}
+    }
+
+    fn calculate_shanten(&self, hand: &TileCounts) -> i8 {
+        let mut num_pair = 0;
+        let mut num_isolated = 0;
+
+        for &c in hand {
+            match c {
+                // A triplet or a quad counts as only one pair,
+                // since the seven pairs must be of different tiles
+                2.. => num_pair += 1,
+                1 => num_isolated += 1,
+                0 => (),
+            }
+        }
+
+        // Adjust for excess isolated tiles
+        if num_pair + num_isolated > NUM_PAIRS {
+            num_isolated = NUM_PAIRS - num_pair;
+        }
+
+        MAX_SHANTEN - num_pair * 2 - num_isolated
+    }
+}
+
+seven_pairs_tests!(SevenPairs);
+shanten_benches!(SevenPairs);
@@ -0,0 +1,3 @@
+use table::Table;
+
+common::criterion_benches!(Table);
@@ -0,0 +1,186 @@
+#![cfg_attr(feature = "nightly", feature(test))]
+
+#[cfg(feature = "nightly")]
+extern crate test;
+
+use common::{MAX_HAND_SIZE, MAX_NUM_TILE, ShantenCalculator, TileCounts};
+use common::{annotated_tests, shanten_benches, shanten_tests, single_suit_tests};
+
+const MAX_NUM_MELD: usize = 4;
+const MAX_NUM_PAIR: usize = 1;
+/// Number of entries for each pattern: (0-4 melds) * (0-1 pair)
+pub const NUM_ENTRY: usize = (MAX_NUM_MELD + 1) * (MAX_NUM_PAIR + 1);
+const NUM_SUIT_TILE: usize = 9;
+const NUM_HONOR_TILE: usize = 7;
+
+/// Minimum number of tiles to draw for each number of melds and pairs.
+///
+/// See [entry_index] for the layout.
+pub type Entry = [u8; NUM_ENTRY];
+
+/// Returns the index in an [Entry] for the number of melds and pairs.
+pub const fn entry_index(num_meld: usize, num_pair: usize) -> usize {
+    num_pair * (MAX_NUM_MELD + 1) + num_meld
+}
+
+const fn weight(i: usize) -> usize {
+    5usize.pow(i as u32)
+}
+
+fn decode<const N: usize>(mut index: usize) -> [u8; N] {
+    std::array::from_fn(|_| {
+        let c = index % 5;
+        index /= 5;
+        c as u8
+    })
+}
+
+/// Returns the index of the pattern of tile counts.
+pub fn encode(counts: &[u8]) -> usize {
+    counts
+        .iter()
+        .rev()
+        .fold(0, |index, &c| index * 5 + c as usize)
+}
+
+/// Marks every pattern consisting of exactly `num_meld` melds starting from `start`
+/// added to `target`.
+fn mark_melds<const N: usize>(
+    target: &mut [u8; N],
+    num_meld: usize,
+    start: usize,
+    is_suit: bool,
+    is_target: &mut [bool],
+) {
+    if num_meld == 0 {
+        if target.iter().all(|&c| c as usize <= MAX_NUM_TILE) {
+            is_target[encode(target)] = true;
+        }
+        return;
+    }
+
+    // Triplets (0..N) and sequences (N..)
+    let num_meld_type = if is_suit { N + N - 2 } else { N };
+    for meld in start..num_meld_type {
+        if meld < N {
+            target[meld] += 3;
+        } else {
+            (meld - N..meld - N + 3).for_each(|i| target[i] += 1);
+        }
+        mark_melds(target, num_meld - 1, meld, is_suit, is_target);
+        if meld < N {
+            target[meld] -= 3;
+        } else {
+            (meld - N..meld - N + 3).for_each(|i| target[i] -= 1);
+        }
+    }
+}
+
+fn build_table<const N: usize>(is_suit: bool) -> Vec<Entry> {
+    let num_pattern = weight(N);
+    let mut table = vec![[0; NUM_ENTRY]; num_pattern];
+
+    for num_pair in 0..=MAX_NUM_PAIR {
+        for num_meld in 0..=MAX_NUM_MELD {
+            let k = entry_index(num_meld, num_pair);
+
+            // Patterns consisting of exactly the melds and pairs
+            let mut contains = vec![false; num_pattern];
+            let mut target = [0; N];
+            if num_pair == 0 {
+                mark_melds(&mut target, num_meld, 0, is_suit, &mut contains);
+            } else {
+                for i in 0..N {
+                    target[i] += 2;
+                    mark_melds(&mut target, num_meld, 0, is_suit, &mut contains);
+                    target[i] -= 2;
+                }
+            }
+
+            // Patterns containing the melds and pairs
+            for index in 0..num_pattern {
+                let counts = decode::<N>(index);
+                contains[index] =
+                    contains[index] || (0..N).any(|i| counts[i] > 0 && contains[index - weight(i)]);
+            }
+
+            // The number of tiles to draw decreases by 1 with a suitable draw
+            for index in (0..num_pattern).rev() {
+                let counts = decode::<N>(index);
+                table[index][k] = if contains[index] {
+                    0
+                } else {
+                    1 + (0..N)
+                        .filter(|&i| (counts[i] as usize) < MAX_NUM_TILE)
+                        .map(|i| table[index + weight(i)][k])
+                        .min()
+                        .unwrap()
+                };
+            }
+        }
+    }
+
+    table
+}
+
+/// Builds the table for the patterns of a suit (5^9 patterns).
+pub fn build_suit_table() -> Vec<Entry> {
+    build_table::<NUM_SUIT_TILE>(true)
+}
+
+/// Builds the table for the patterns of the honors (5^7 patterns).
+pub fn build_honor_table() -> Vec<Entry> {
+    build_table::<NUM_HONOR_TILE>(false)
+}
+
+/// Combines the entries of two groups of tiles by min-plus convolution.
+pub fn combine(a: &Entry, b: &Entry) -> Entry {
+    let mut result = [u8::MAX; NUM_ENTRY];
+    for p1 in 0..=MAX_NUM_PAIR {
+        for p2 in 0..=MAX_NUM_PAIR - p1 {
+            for m1 in 0..=MAX_NUM_MELD {
+                for m2 in 0..=MAX_NUM_MELD - m1 {
+                    let k = entry_index(m1 + m2, p1 + p2);
+                    let n = a[entry_index(m1, p1)] + b[entry_index(m2, p2)];
+                    result[k] = result[k].min(n);
+                }
+            }
+        }
+    }
+    result
+}
+
+pub struct Table {
+    suit_table: Vec<Entry>,
+    honor_table: Vec<Entry>,
+}
+
+impl ShantenCalculator for Table {
+    fn new() -> Self {
+        Table {
+            suit_table: build_suit_table(),
+            honor_table: build_honor_table(),
+        }
+    }
+
+    fn calculate_shanten(&self, hand: &TileCounts) -> i8 {
+        let sum: u8 = hand.iter().sum();
+        debug_assert!(sum as usize <= MAX_HAND_SIZE, "too many tiles: {sum}");
+        let num_call = 4u8.saturating_sub(sum / 3);
+        let num_meld = (4 - num_call) as usize;
+
+        let entry = [0, 9, 18]
+            .map(|base| &self.suit_table[encode(&hand[base..base + NUM_SUIT_TILE])])
+            .iter()
+            .fold(self.honor_table[encode(&hand[27..])], |entry, suit| {
+                combine(&entry, suit)
+            });
+
+        entry[entry_index(num_meld, 1)] as i8 - 1
+    }
+}
+
+shanten_tests!(Table);
+single_suit_tests!(Table);
+annotated_tests!(Table);
+shanten_benches!(Table);
@@ -0,0 +1,3 @@
+use table_embedded::TableEmbedded;
+
+common::criterion_benches!(TableEmbedded);
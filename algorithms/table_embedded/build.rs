@@ -0,0 +1,41 @@
+// `CompressedTable::get` and `unpack` are only used by the library
+#[allow(dead_code)]
+#[path = "src/packing.rs"]
+mod packing;
+
+use packing::{CompressedTable, compress};
+use std::path::Path;
+
+fn main() -> std::io::Result<()> {
+    println!("cargo::rerun-if-changed=build.rs");
+    println!("cargo::rerun-if-changed=src/packing.rs");
+
+    let out_dir = std::env::var("OUT_DIR").unwrap();
+    let out_dir = Path::new(&out_dir);
+
+    let (suit_rows, suit_index) = compress(&table::build_suit_table());
+    let (honor_rows, honor_index) = compress(&table::build_honor_table());
+    std::fs::write(out_dir.join("suit_rows.bin"), &suit_rows)?;
+    std::fs::write(out_dir.join("suit_index.bin"), &suit_index)?;
+    std::fs::write(out_dir.join("honor_rows.bin"), &honor_rows)?;
+    std::fs::write(out_dir.join("honor_index.bin"), &honor_index)?;
+
+    let suit_table = CompressedTable {
+        rows: &suit_rows,
+        index: &suit_index,
+    };
+    let honor_table = CompressedTable {
+        rows: &honor_rows,
+        index: &honor_index,
+    };
+    std::fs::write(
+        out_dir.join("checksums.rs"),
+        format!(
+            "const SUIT_TABLE_CHECKSUM: u64 = {:#018x};\nconst HONOR_TABLE_CHECKSUM: u64 = {:#018x};\n",
+            suit_table.checksum(),
+            honor_table.checksum()
+        ),
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,102 @@
+#![cfg_attr(feature = "nightly", feature(test))]
+
+#[cfg(feature = "nightly")]
+extern crate test;
+
+mod packing;
+
+use common::{MAX_HAND_SIZE, ShantenCalculator, TileCounts};
+use common::{annotated_tests, shanten_benches, shanten_tests, single_suit_tests};
+use packing::{CompressedTable, compress};
+use table::{combine, encode, entry_index};
+
+static SUIT_TABLE: CompressedTable = CompressedTable {
+    rows: include_bytes!(concat!(env!("OUT_DIR"), "/suit_rows.bin")),
+    index: include_bytes!(concat!(env!("OUT_DIR"), "/suit_index.bin")),
+};
+static HONOR_TABLE: CompressedTable = CompressedTable {
+    rows: include_bytes!(concat!(env!("OUT_DIR"), "/honor_rows.bin")),
+    index: include_bytes!(concat!(env!("OUT_DIR"), "/honor_index.bin")),
+};
+include!(concat!(env!("OUT_DIR"), "/checksums.rs"));
+
+/// Error returned when the embedded tables are broken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TableError {
+    /// The checksum of the embedded suit table does not match.
+    SuitTableChecksum,
+    /// The checksum of the embedded honor table does not match.
+    HonorTableChecksum,
+    /// The embedded suit table differs from the regenerated one.
+    SuitTableMismatch,
+    /// The embedded honor table differs from the regenerated one.
+    HonorTableMismatch,
+}
+
+/// Verifies the checksums of the embedded tables.
+///
+/// This is cheap and can be called at startup.
+pub fn verify_checksums() -> Result<(), TableError> {
+    if SUIT_TABLE.checksum() != SUIT_TABLE_CHECKSUM {
+        return Err(TableError::SuitTableChecksum);
+    }
+    if HONOR_TABLE.checksum() != HONOR_TABLE_CHECKSUM {
+        return Err(TableError::HonorTableChecksum);
+    }
+    Ok(())
+}
+
+/// Regenerates the tables at runtime and verifies that the embedded tables match them.
+///
+/// This takes as long as building the tables of the [table] crate.
+pub fn verify_tables() -> Result<(), TableError> {
+    verify_checksums()?;
+    let (rows, index) = compress(&table::build_suit_table());
+    if rows != SUIT_TABLE.rows || index != SUIT_TABLE.index {
+        return Err(TableError::SuitTableMismatch);
+    }
+    let (rows, index) = compress(&table::build_honor_table());
+    if rows != HONOR_TABLE.rows || index != HONOR_TABLE.index {
+        return Err(TableError::HonorTableMismatch);
+    }
+    Ok(())
+}
+
+pub struct TableEmbedded {}
+
+impl ShantenCalculator for TableEmbedded {
+    fn new() -> Self {
+        TableEmbedded {}
+    }
+
+    fn calculate_shanten(&self, hand: &TileCounts) -> i8 {
+        let sum: u8 = hand.iter().sum();
+        debug_assert!(sum as usize <= MAX_HAND_SIZE, "too many tiles: {sum}");
+        let num_call = 4u8.saturating_sub(sum / 3);
+        let num_meld = (4 - num_call) as usize;
+
+        let entry = [0, 9, 18]
+            .map(|base| SUIT_TABLE.get(encode(&hand[base..base + 9])))
+            .iter()
+            .fold(HONOR_TABLE.get(encode(&hand[27..])), |entry, suit| {
+                combine(&entry, suit)
+            });
+
+        entry[entry_index(num_meld, 1)] as i8 - 1
+    }
+}
+
+#[cfg(test)]
+mod verification_tests {
+    use super::*;
+
+    #[test]
+    fn test_verify_tables() {
+        assert_eq!(verify_tables(), Ok(()));
+    }
+}
+
+shanten_tests!(TableEmbedded);
+single_suit_tests!(TableEmbedded);
+annotated_tests!(TableEmbedded);
+shanten_benches!(TableEmbedded);
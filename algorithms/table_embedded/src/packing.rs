@@ -0,0 +1,111 @@
+//! Packing of the tables, shared by the build script and the library.
+
+use std::collections::HashMap;
+
+use table::{Entry, NUM_ENTRY};
+
+/// Number of bytes of a packed entry (4 bits for each value).
+pub const PACKED_ENTRY_SIZE: usize = NUM_ENTRY / 2;
+
+/// A table stored as its distinct entries and the index of the entry of each pattern.
+///
+/// The tables have only a few hundred distinct entries, so an index fits in a byte.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CompressedTable<'a> {
+    /// Distinct entries in the order of their first pattern, packed by [pack]
+    pub rows: &'a [u8],
+    /// Index of the entry of each pattern in `rows`
+    pub index: &'a [u8],
+}
+
+impl CompressedTable<'_> {
+    /// Returns the entry of the pattern.
+    pub fn get(&self, pattern: usize) -> Entry {
+        unpack(self.rows, self.index[pattern] as usize)
+    }
+
+    /// Calculates the checksum of the index followed by the rows.
+    pub fn checksum(&self) -> u64 {
+        checksum(self.index.iter().chain(self.rows))
+    }
+}
+
+/// Compresses a table into its distinct entries, packed by [pack],
+/// and the index of the entry of each pattern.
+///
+/// # Panics
+///
+/// Panics if the table has more than 256 distinct entries.
+pub fn compress(table: &[Entry]) -> (Vec<u8>, Vec<u8>) {
+    let mut rows = Vec::new();
+    let mut ids = HashMap::new();
+    let mut index = Vec::with_capacity(table.len());
+
+    for entry in table {
+        let id = *ids.entry(*entry).or_insert_with(|| {
+            rows.push(*entry);
+            rows.len() - 1
+        });
+        index.push(u8::try_from(id).expect("too many distinct entries"));
+    }
+
+    (pack(&rows), index)
+}
+
+/// Packs the entries of a table into 4 bits for each value.
+///
+/// Every value fits in 4 bits, since at most 14 tiles are needed.
+pub fn pack(table: &[Entry]) -> Vec<u8> {
+    table
+        .iter()
+        .flat_map(|entry| entry.chunks(2).map(|v| v[0] | (v[1] << 4)))
+        .collect()
+}
+
+/// Unpacks the entry of the pattern with the index.
+pub fn unpack(packed: &[u8], index: usize) -> Entry {
+    let bytes = &packed[index * PACKED_ENTRY_SIZE..(index + 1) * PACKED_ENTRY_SIZE];
+    std::array::from_fn(|k| (bytes[k / 2] >> (4 * (k % 2))) & 0xF)
+}
+
+/// Calculates the 64-bit FNV-1a hash of the data.
+pub fn checksum<'a>(data: impl IntoIterator<Item = &'a u8>) -> u64 {
+    data.into_iter().fold(0xcbf2_9ce4_8422_2325, |hash, &b| {
+        (hash ^ b as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pack_unpack() {
+        let table = [
+            [0, 1, 2, 3, 4, 5, 6, 7, 8, 9],
+            [14, 13, 12, 11, 10, 0, 0, 0, 0, 15],
+        ];
+        let packed = pack(&table);
+        assert_eq!(packed.len(), 2 * PACKED_ENTRY_SIZE);
+        assert_eq!(unpack(&packed, 0), table[0]);
+        assert_eq!(unpack(&packed, 1), table[1]);
+    }
+
+    #[test]
+    fn test_compress() {
+        let a = [0, 1, 2, 3, 4, 5, 6, 7, 8, 9];
+        let b = [14, 13, 12, 11, 10, 0, 0, 0, 0, 15];
+        let table = [a, b, b, a, b];
+        let (rows, index) = compress(&table);
+        assert_eq!(rows, pack(&[a, b]));
+        assert_eq!(index, [0, 1, 1, 0, 1]);
+
+        let compressed = CompressedTable {
+            rows: &rows,
+            index: &index,
+        };
+        for (pattern, entry) in table.iter().enumerate() {
+            assert_eq!(compressed.get(pattern), *entry);
+        }
+    }
+}
@@ -0,0 +1,3 @@
+use thirteen_orphans::ThirteenOrphans;
+
+common::criterion_benches!(ThirteenOrphans);
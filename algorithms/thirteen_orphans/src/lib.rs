@@ -0,0 +1,38 @@
+#![cfg_attr(feature = "nightly", feature(test))]
+
+#[cfg(feature = "nightly")]
+extern crate test;
+
+use common::{ShantenCalculator, TERMINAL_AND_HONOR_TILES, TileCounts};
+use common::{shanten_benches, thirteen_orphans_tests};
+
+const MAX_SHANTEN: i8 = 13;
+
+pub struct ThirteenOrphans {}
+
+impl ShantenCalculator for ThirteenOrphans {
+    fn new() -> Self {
+        ThirteenOrphans {}
+    }
+
+    fn calculate_shanten(&self, hand: &TileCounts) -> i8 {
+        let mut num_kind = 0;
+        let mut has_pair = false;
+
+        for i in TERMINAL_AND_HONOR_TILES {
+            match hand[i] {
+                2.. => {
+                    num_kind += 1;
+                    has_pair = true;
+                }
+                1 => num_kind += 1,
+                0 => (),
+            }
+        }
+
+        MAX_SHANTEN - num_kind - if has_pair { 1 } else { 0 }
+    }
+}
+
+thirteen_orphans_tests!(ThirteenOrphans);
+shanten_benches!(ThirteenOrphans);
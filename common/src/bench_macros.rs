@@ -14,14 +14,22 @@
 /// - `bench_shanten_half_flush_10000`: 10,000 half flush hands
 /// - `bench_shanten_full_flush_10000`: 10,000 full flush hands
 /// - `bench_shanten_thirteen_orphans_10000`: 10,000 thirteen orphans hands
+/// - `bench_shanten_game_10000`: 10,000 hands in the middle of a game, generated by self-play
+/// - `bench_shanten_bucket_<shanten>_1000`: 1,000 hands with each shanten number from -1
+///   (`minus_1`) to 6, to compare the cost of low and high shanten hands
+/// - `bench_shanten_batch_*_10000`: the same hands passed to `calculate_shanten_batch`
+/// - `bench_shanten_batch_parallel_*_10000`: the same hands passed to
+///   `calculate_shanten_batch_parallel` (only with the `rayon` feature of `common`)
 ///
 /// Each function loads the corresponding resource file from the `resources/` directory and
-/// benchmarks the `calculate_shanten` method of `$calculator_type` for all hands in the file.
+/// benchmarks the `calculate_shanten` method of `$calculator_type` for all hands in the file,
+/// one hand at a time or as a batch.
 ///
 /// # Example
 ///
 /// ```
-/// #![feature(test)]
+/// #![cfg_attr(feature = "nightly", feature(test))]
+/// #[cfg(feature = "nightly")]
 /// extern crate test;
 /// use common::shanten_benches;
 ///
@@ -31,63 +39,32 @@
 /// # Notes
 ///
 /// - Running the benchmarks requires nightly Rust and the `test` crate.
-/// - Each line in the input file must consist of 14 tile indices (0–33).
-///   The macro will panic if any line is malformed or if the number of lines is incorrect.
+///   The benchmarks are only generated with the `nightly` feature of the calling crate,
+///   so that the crate also builds on stable Rust.
+///   See `criterion_benches!` for benchmarks on stable Rust.
+/// - To measure the parallel path, enable the feature with `--features nightly,common/rayon`.
+/// - The input files are read by [`load_hands`](crate::load_hands) from
+///   [`RESOURCES_DIR`](crate::RESOURCES_DIR).
+///   See [`parse_hand_line`](crate::parse_hand_line) for the format of each line.
+///   The benchmarks panic with the line number if any line is malformed.
 #[macro_export]
 macro_rules! shanten_benches {
     ($calculator_type:ty) => {
-        #[cfg(test)]
+        #[cfg(all(test, feature = "nightly"))]
         mod benches {
             use super::*;
-            use std::fs::File;
-            use std::io::{BufRead, BufReader};
             use test::Bencher;
             use $crate::{ShantenCalculator, TileCounts};
 
-            fn parse_hands<I: Iterator<Item = String>>(mut lines: I) -> Vec<TileCounts> {
-                const NUM_CASES: usize = 10_000;
-                let mut hands = Vec::with_capacity(NUM_CASES);
-                while let Some(line) = lines.next() {
-                    let tokens: Vec<_> = line
-                        .split_whitespace()
-                        .filter_map(|s| s.parse::<usize>().ok())
-                        .collect();
-                    if tokens.len() != 14 {
-                        panic!(
-                            "invalid input line: expected 14 tokens, got {}: '{}'",
-                            tokens.len(),
-                            line
-                        );
-                    }
-                    let mut counts = [0u8; 34];
-                    for &tid in &tokens {
-                        counts[tid] += 1;
-                    }
-                    hands.push(counts);
-                }
-
-                if hands.len() != NUM_CASES {
-                    panic!(
-                        "invalid input file: expected {} lines, got {}",
-                        NUM_CASES,
-                        hands.len()
-                    );
-                }
-
-                hands
-            }
-
             fn load_hands(filename: &str) -> Vec<TileCounts> {
-                let path = std::path::Path::new(filename);
-                let file = File::open(&path).expect(&format!("hands file not found: {:?}", path));
-                let lines = BufReader::new(file).lines().filter_map(Result::ok);
-                parse_hands(lines)
+                let path = $crate::resource_path(filename);
+                $crate::load_hands(&path).unwrap_or_else(|e| panic!("{}: {e}", path.display()))
             }
 
             #[bench]
             fn bench_shanten_normal_10000(b: &mut Bencher) {
                 let calculator = <$calculator_type as $crate::ShantenCalculator>::new();
-                let hands = load_hands("../../resources/hands_normal_10000.txt");
+                let hands = load_hands("hands_normal_10000.txt");
                 b.iter(|| {
                     for hand in &hands {
                         _ = $crate::ShantenCalculator::calculate_shanten(&calculator, hand);
@@ -98,7 +75,7 @@ macro_rules! shanten_benches {
             #[bench]
             fn bench_shanten_half_flush_10000(b: &mut Bencher) {
                 let calculator = <$calculator_type as $crate::ShantenCalculator>::new();
-                let hands = load_hands("../../resources/hands_half_flush_10000.txt");
+                let hands = load_hands("hands_half_flush_10000.txt");
                 b.iter(|| {
                     for hand in &hands {
                         _ = $crate::ShantenCalculator::calculate_shanten(&calculator, hand);
@@ -109,7 +86,7 @@ macro_rules! shanten_benches {
             #[bench]
             fn bench_shanten_full_flush_10000(b: &mut Bencher) {
                 let calculator = <$calculator_type as $crate::ShantenCalculator>::new();
-                let hands = load_hands("../../resources/hands_full_flush_10000.txt");
+                let hands = load_hands("hands_full_flush_10000.txt");
                 b.iter(|| {
                     for hand in &hands {
                         _ = $crate::ShantenCalculator::calculate_shanten(&calculator, hand);
@@ -120,7 +97,219 @@ macro_rules! shanten_benches {
             #[bench]
             fn bench_shanten_thirteen_orphans_10000(b: &mut Bencher) {
                 let calculator = <$calculator_type as $crate::ShantenCalculator>::new();
-                let hands = load_hands("../../resources/hands_thirteen_orphans_10000.txt");
+                let hands = load_hands("hands_thirteen_orphans_10000.txt");
+                b.iter(|| {
+                    for hand in &hands {
+                        _ = $crate::ShantenCalculator::calculate_shanten(&calculator, hand);
+                    }
+                });
+            }
+
+            #[bench]
+            fn bench_shanten_game_10000(b: &mut Bencher) {
+                let calculator = <$calculator_type as $crate::ShantenCalculator>::new();
+                let hands = load_hands("hands_game_10000.txt");
+                b.iter(|| {
+                    for hand in &hands {
+                        _ = $crate::ShantenCalculator::calculate_shanten(&calculator, hand);
+                    }
+                });
+            }
+
+            fn bench_batch(b: &mut Bencher, filename: &str) {
+                let calculator = <$calculator_type as $crate::ShantenCalculator>::new();
+                let hands = load_hands(filename);
+                let mut shantens = vec![0; hands.len()];
+                b.iter(|| {
+                    $crate::ShantenCalculator::calculate_shanten_batch(
+                        &calculator,
+                        &hands,
+                        &mut shantens,
+                    );
+                });
+            }
+
+            #[bench]
+            fn bench_shanten_batch_normal_10000(b: &mut Bencher) {
+                bench_batch(b, "hands_normal_10000.txt");
+            }
+
+            #[bench]
+            fn bench_shanten_batch_half_flush_10000(b: &mut Bencher) {
+                bench_batch(b, "hands_half_flush_10000.txt");
+            }
+
+            #[bench]
+            fn bench_shanten_batch_full_flush_10000(b: &mut Bencher) {
+                bench_batch(b, "hands_full_flush_10000.txt");
+            }
+
+            #[bench]
+            fn bench_shanten_batch_thirteen_orphans_10000(b: &mut Bencher) {
+                bench_batch(b, "hands_thirteen_orphans_10000.txt");
+            }
+
+            #[bench]
+            fn bench_shanten_batch_game_10000(b: &mut Bencher) {
+                bench_batch(b, "hands_game_10000.txt");
+            }
+
+            fn bench_shanten_bucket(b: &mut Bencher, shanten: i8) {
+                let calculator = <$calculator_type as $crate::ShantenCalculator>::new();
+                let hands = load_hands(&format!("hands_shanten_{shanten}_1000.txt"));
+                b.iter(|| {
+                    for hand in &hands {
+                        _ = $crate::ShantenCalculator::calculate_shanten(&calculator, hand);
+                    }
+                });
+            }
+
+            #[bench]
+            fn bench_shanten_bucket_minus_1_1000(b: &mut Bencher) {
+                bench_shanten_bucket(b, -1);
+            }
+
+            #[bench]
+            fn bench_shanten_bucket_0_1000(b: &mut Bencher) {
+                bench_shanten_bucket(b, 0);
+            }
+
+            #[bench]
+            fn bench_shanten_bucket_1_1000(b: &mut Bencher) {
+                bench_shanten_bucket(b, 1);
+            }
+
+            #[bench]
+            fn bench_shanten_bucket_2_1000(b: &mut Bencher) {
+                bench_shanten_bucket(b, 2);
+            }
+
+            #[bench]
+            fn bench_shanten_bucket_3_1000(b: &mut Bencher) {
+                bench_shanten_bucket(b, 3);
+            }
+
+            #[bench]
+            fn bench_shanten_bucket_4_1000(b: &mut Bencher) {
+                bench_shanten_bucket(b, 4);
+            }
+
+            #[bench]
+            fn bench_shanten_bucket_5_1000(b: &mut Bencher) {
+                bench_shanten_bucket(b, 5);
+            }
+
+            #[bench]
+            fn bench_shanten_bucket_6_1000(b: &mut Bencher) {
+                bench_shanten_bucket(b, 6);
+            }
+
+            $crate::shanten_parallel_benches!($calculator_type);
+        }
+    };
+}
+
+/// Macro to generate benchmark functions for the parallel batch path.
+///
+/// # Note
+///
+/// This macro is exported for use in [`shanten_benches!`], but users should not call it directly.
+/// It expands to nothing unless the `rayon` feature of `common` is enabled.
+#[cfg(feature = "rayon")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! shanten_parallel_benches {
+    ($calculator_type:ty) => {
+        fn bench_batch_parallel(b: &mut Bencher, filename: &str) {
+            let calculator = <$calculator_type as $crate::ShantenCalculator>::new();
+            let hands = load_hands(filename);
+            let mut shantens = vec![0; hands.len()];
+            b.iter(|| {
+                $crate::ShantenCalculator::calculate_shanten_batch_parallel(
+                    &calculator,
+                    &hands,
+                    &mut shantens,
+                );
+            });
+        }
+
+        #[bench]
+        fn bench_shanten_batch_parallel_normal_10000(b: &mut Bencher) {
+            bench_batch_parallel(b, "hands_normal_10000.txt");
+        }
+
+        #[bench]
+        fn bench_shanten_batch_parallel_half_flush_10000(b: &mut Bencher) {
+            bench_batch_parallel(b, "hands_half_flush_10000.txt");
+        }
+
+        #[bench]
+        fn bench_shanten_batch_parallel_full_flush_10000(b: &mut Bencher) {
+            bench_batch_parallel(b, "hands_full_flush_10000.txt");
+        }
+
+        #[bench]
+        fn bench_shanten_batch_parallel_thirteen_orphans_10000(b: &mut Bencher) {
+            bench_batch_parallel(b, "hands_thirteen_orphans_10000.txt");
+        }
+
+        #[bench]
+        fn bench_shanten_batch_parallel_game_10000(b: &mut Bencher) {
+            bench_batch_parallel(b, "hands_game_10000.txt");
+        }
+    };
+}
+
+/// Macro to generate benchmark functions for the parallel batch path.
+///
+/// # Note
+///
+/// This macro is exported for use in [`shanten_benches!`], but users should not call it directly.
+/// It expands to nothing unless the `rayon` feature of `common` is enabled.
+#[cfg(not(feature = "rayon"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! shanten_parallel_benches {
+    ($calculator_type:ty) => {};
+}
+
+/// Macro to generate a benchmark function for a specified `ShantenCalculator` type
+/// with three-player mahjong (sanma) hands.
+///
+/// The benchmark reads `resources/hands_sanma_10000.txt`, which contains 14-tile hands
+/// drawn from the 108-tile wall without 2m–8m,
+/// and measures the `calculate_shanten` method of `$calculator_type` for all hands in the file.
+/// Like [`shanten_benches!`], it is only generated with the `nightly` feature of the calling crate.
+///
+/// # Arguments
+///
+/// * `$calculator_type` - A type that implements the `ShantenCalculator` trait for sanma hands
+///
+/// # Example
+///
+/// ```
+/// #![cfg_attr(feature = "nightly", feature(test))]
+/// #[cfg(feature = "nightly")]
+/// extern crate test;
+/// use common::sanma_benches;
+///
+/// sanma_benches!(Sanma<YourAlgorithm>);
+/// ```
+#[macro_export]
+macro_rules! sanma_benches {
+    ($calculator_type:ty) => {
+        #[cfg(all(test, feature = "nightly"))]
+        mod sanma_benches {
+            use super::*;
+            use test::Bencher;
+
+            #[bench]
+            fn bench_shanten_sanma_10000(b: &mut Bencher) {
+                let calculator = <$calculator_type as $crate::ShantenCalculator>::new();
+                let path = $crate::resource_path("hands_sanma_10000.txt");
+                let hands =
+                    $crate::load_hands(&path).unwrap_or_else(|e| panic!("{}: {e}", path.display()));
+
                 b.iter(|| {
                     for hand in &hands {
                         _ = $crate::ShantenCalculator::calculate_shanten(&calculator, hand);
@@ -130,3 +319,105 @@ macro_rules! shanten_benches {
         }
     };
 }
+
+/// Macro to generate a [criterion](https://docs.rs/criterion) benchmark harness
+/// for a specified `ShantenCalculator` type.
+///
+/// Unlike [`shanten_benches!`], the harness builds on stable Rust.
+/// It reads the same resource files and reports the throughput in hands per second
+/// (shown as `elem/s` by criterion) for each hand category.
+/// This macro is available with the `criterion` feature.
+///
+/// # Arguments
+///
+/// * `$calculator_type` - A type that implements the `ShantenCalculator` trait
+/// * `[$category, ...]` - Optional hand categories, read from `resources/hands_<category>_10000.txt`.
+///   Defaults to `"normal"`, `"half_flush"`, `"full_flush"`, `"thirteen_orphans"` and `"game"`.
+///
+/// # Generated Benchmarks
+///
+/// For each category, the benchmark group `shanten/<category>` contains:
+///
+/// - `calculate_shanten`: the hands passed one at a time
+/// - `calculate_shanten_batch`: the hands passed as a batch
+///
+/// # Example
+///
+/// Put the following in `benches/criterion.rs` of your crate:
+///
+/// ```ignore
+/// use your_algorithm::YourAlgorithm;
+///
+/// common::criterion_benches!(YourAlgorithm);
+/// ```
+///
+/// and register it in `Cargo.toml`:
+///
+/// ```toml
+/// [features]
+/// criterion = ["common/criterion"]
+///
+/// [[bench]]
+/// name = "criterion"
+/// harness = false
+/// required-features = ["criterion"]
+/// ```
+///
+/// # Notes
+///
+/// - Run the benchmarks with `cargo bench --features criterion`.
+/// - The input files are read by [`load_hands`](crate::load_hands) from
+///   [`RESOURCES_DIR`](crate::RESOURCES_DIR).
+///   See [`parse_hand_line`](crate::parse_hand_line) for the format of each line.
+///   The benchmarks panic with the line number if any line is malformed.
+#[cfg(feature = "criterion")]
+#[macro_export]
+macro_rules! criterion_benches {
+    ($calculator_type:ty) => {
+        $crate::criterion_benches!(
+            $calculator_type,
+            ["normal", "half_flush", "full_flush", "thirteen_orphans", "game"]
+        );
+    };
+    ($calculator_type:ty, [$($category:literal),+ $(,)?]) => {
+        fn load_hands(category: &str) -> Vec<$crate::TileCounts> {
+            let path = $crate::resource_path(&format!("hands_{category}_10000.txt"));
+            $crate::load_hands(&path).unwrap_or_else(|e| panic!("{}: {e}", path.display()))
+        }
+
+        fn bench_shanten(c: &mut $crate::criterion::Criterion) {
+            let calculator = <$calculator_type as $crate::ShantenCalculator>::new();
+            for category in [$($category),+] {
+                let hands = load_hands(category);
+                let mut shantens = vec![0; hands.len()];
+
+                let mut group = c.benchmark_group(format!("shanten/{category}"));
+                group.throughput($crate::criterion::Throughput::Elements(hands.len() as u64));
+                group.bench_function("calculate_shanten", |b| {
+                    b.iter(|| {
+                        for hand in &hands {
+                            std::hint::black_box($crate::ShantenCalculator::calculate_shanten(
+                                &calculator,
+                                std::hint::black_box(hand),
+                            ));
+                        }
+                    })
+                });
+                group.bench_function("calculate_shanten_batch", |b| {
+                    b.iter(|| {
+                        $crate::ShantenCalculator::calculate_shanten_batch(
+                            &calculator,
+                            std::hint::black_box(&hands),
+                            &mut shantens,
+                        );
+                        std::hint::black_box(&shantens);
+                    })
+                });
+                group.finish();
+            }
+        }
+
+        $crate::criterion::criterion_group!(benches, bench_shanten);
+        $crate::criterion::criterion_main!(benches);
+    };
+}
@@ -9,3 +9,12 @@ pub const MIN_HAND_SIZE: usize = 1;
 
 /// Maximum number of tiles in a hand
 pub const MAX_HAND_SIZE: usize = 14;
+
+/// Indices of terminal and honor tiles (1m, 9m, 1p, 9p, 1s, 9s and 1z–7z)
+pub const TERMINAL_AND_HONOR_TILES: [usize; 13] = [0, 8, 9, 17, 18, 26, 27, 28, 29, 30, 31, 32, 33];
+
+/// Indices of the tiles removed in three-player mahjong (2m–8m)
+pub const SANMA_EXCLUDED_TILES: [usize; 7] = [1, 2, 3, 4, 5, 6, 7];
+
+/// Number of tile types in three-player mahjong
+pub const NUM_SANMA_TILE_TYPE: usize = NUM_TILE_TYPE - SANMA_EXCLUDED_TILES.len();
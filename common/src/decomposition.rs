@@ -0,0 +1,134 @@
+use crate::constants::NUM_TILE_TYPE;
+use crate::types::TileCounts;
+
+/// A block extracted from a hand during block decomposition.
+///
+/// Each variant holds the index of the lowest tile in the block.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Block {
+    /// Triplet (e.g. `111m`)
+    Triplet(usize),
+    /// Sequence (e.g. `123m`)
+    Sequence(usize),
+    /// Pair as a triplet candidate (e.g. `11m`)
+    Pair(usize),
+    /// Edge joint or open joint (e.g. `12m`, `23m`)
+    Joint(usize),
+    /// Middle joint (e.g. `13m`)
+    MiddleJoint(usize),
+}
+
+impl Block {
+    /// Returns `true` if the block is a meld (triplet or sequence).
+    pub fn is_meld(&self) -> bool {
+        matches!(self, Block::Triplet(_) | Block::Sequence(_))
+    }
+
+    /// Returns the indices of the tiles in the block in ascending order.
+    pub fn tiles(&self) -> Vec<usize> {
+        match *self {
+            Block::Triplet(i) => vec![i, i, i],
+            Block::Sequence(i) => vec![i, i + 1, i + 2],
+            Block::Pair(i) => vec![i, i],
+            Block::Joint(i) => vec![i, i + 1],
+            Block::MiddleJoint(i) => vec![i, i + 2],
+        }
+    }
+}
+
+/// A decomposition of a hand into a pair, melds, meld candidates and isolated tiles.
+///
+/// Melds formed by calls are not included, since they are not part of [TileCounts].
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Decomposition {
+    /// Index of the tile used as the pair, if any.
+    pub pair: Option<usize>,
+    /// Melds (triplets and sequences), sorted by kind and then by tile index.
+    pub melds: Vec<Block>,
+    /// Meld candidates (pairs, joints and middle joints), sorted by kind and then by tile index.
+    pub meld_cands: Vec<Block>,
+    /// Indices of the isolated tiles in ascending order.
+    pub isolated: Vec<usize>,
+}
+
+impl Decomposition {
+    /// Creates a new instance from the extracted blocks and the remaining tiles.
+    ///
+    /// # Arguments
+    ///
+    /// * `pair` - Index of the tile used as the pair, if any
+    /// * `blocks` - Extracted melds and meld candidates in any order
+    /// * `isolated` - Reference to a [TileCounts] struct representing the remaining tiles
+    pub fn new(pair: Option<usize>, blocks: &[Block], isolated: &TileCounts) -> Self {
+        let (mut melds, mut meld_cands): (Vec<_>, Vec<_>) =
+            blocks.iter().partition(|b| b.is_meld());
+        melds.sort_unstable();
+        meld_cands.sort_unstable();
+        let isolated = (0..NUM_TILE_TYPE)
+            .flat_map(|i| std::iter::repeat_n(i, isolated[i] as usize))
+            .collect();
+
+        Decomposition {
+            pair,
+            melds,
+            meld_cands,
+            isolated,
+        }
+    }
+
+    /// Returns the tile counts of all tiles in the decomposition.
+    pub fn to_tile_counts(&self) -> TileCounts {
+        let mut counts = [0; NUM_TILE_TYPE];
+        if let Some(i) = self.pair {
+            counts[i] += 2;
+        }
+        for block in self.melds.iter().chain(&self.meld_cands) {
+            for i in block.tiles() {
+                counts[i] += 1;
+            }
+        }
+        for &i in &self.isolated {
+            counts[i] += 1;
+        }
+        counts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TileCountsExt;
+
+    #[test]
+    fn test_new_sorts_blocks() {
+        let isolated = TileCounts::from_code("5z");
+        let blocks = [
+            Block::Joint(9),
+            Block::Sequence(0),
+            Block::Pair(28),
+            Block::Triplet(27),
+        ];
+        let decomposition = Decomposition::new(Some(18), &blocks, &isolated);
+        assert_eq!(
+            decomposition.melds,
+            [Block::Triplet(27), Block::Sequence(0)]
+        );
+        assert_eq!(decomposition.meld_cands, [Block::Pair(28), Block::Joint(9)]);
+        assert_eq!(decomposition.isolated, [31]);
+    }
+
+    #[test]
+    fn test_to_tile_counts() {
+        let isolated = TileCounts::from_code("55z");
+        let blocks = [
+            Block::Sequence(0),
+            Block::MiddleJoint(9),
+            Block::Triplet(27),
+        ];
+        let decomposition = Decomposition::new(Some(18), &blocks, &isolated);
+        assert_eq!(
+            decomposition.to_tile_counts(),
+            TileCounts::from_code("123m13p11s11155z")
+        );
+    }
+}
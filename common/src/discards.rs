@@ -0,0 +1,69 @@
+use crate::constants::NUM_TILE_TYPE;
+use crate::necessary_tiles::NecessaryTiles;
+use crate::traits::ShantenCalculator;
+use crate::types::TileCounts;
+
+/// A discard candidate of a 3n+2 hand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Discard {
+    /// Index of the discarded tile type (0–33).
+    pub tile: usize,
+    /// Necessary tiles of the hand after the discard.
+    ///
+    /// `shanten` is the shanten number after the discard.
+    /// `num_tiles` counts the remaining copies excluding those in the hand before the discard,
+    /// so the discarded tile itself is not counted as a remaining copy.
+    pub necessary_tiles: NecessaryTiles,
+}
+
+/// Calculates every discard candidate of a 3n+2 hand.
+///
+/// This is the default implementation of [`ShantenCalculator::calculate_discards`].
+/// It calls [`ShantenCalculator::calculate_necessary_tiles`] once for each tile type in the hand.
+///
+/// # Arguments
+///
+/// * `calculator` - The calculator used to calculate the necessary tiles
+/// * `hand` - Reference to a [TileCounts] struct representing the hand's tile counts.
+///   The number of tiles must be 3n+2.
+///
+/// # Returns
+///
+/// One [Discard] for each tile type in the hand, sorted best-first:
+/// by the shanten number in ascending order, then by the number of remaining copies of the
+/// necessary tiles in descending order, then by the tile index in ascending order.
+pub fn calculate_discards<C: ShantenCalculator + ?Sized>(
+    calculator: &C,
+    hand: &TileCounts,
+) -> Vec<Discard> {
+    let mut hand_clone = *hand;
+    let mut discards = Vec::with_capacity(NUM_TILE_TYPE);
+
+    for i in 0..NUM_TILE_TYPE {
+        if hand_clone[i] == 0 {
+            continue;
+        }
+
+        hand_clone[i] -= 1;
+        let necessary_tiles = calculator.calculate_necessary_tiles(&hand_clone);
+        hand_clone[i] += 1;
+
+        discards.push(Discard {
+            tile: i,
+            necessary_tiles: NecessaryTiles::new(
+                necessary_tiles.shanten,
+                necessary_tiles.tiles,
+                hand,
+            ),
+        });
+    }
+
+    discards.sort_by_key(|d| {
+        (
+            d.necessary_tiles.shanten,
+            std::cmp::Reverse(d.necessary_tiles.num_tiles),
+            d.tile,
+        )
+    });
+    discards
+}
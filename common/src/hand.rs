@@ -0,0 +1,672 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::constants::{
+    MAX_HAND_SIZE, MAX_NUM_TILE, MIN_HAND_SIZE, NUM_TILE_TYPE, SANMA_EXCLUDED_TILES,
+};
+use crate::tenhou::{ParseError, parse_hand, to_code};
+use crate::types::TileCounts;
+
+/// Maximum number of melds in a hand.
+const MAX_NUM_MELD: usize = 4;
+
+/// The set of tiles used in a game.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TileSet {
+    /// All 136 tiles of four-player mahjong.
+    #[default]
+    Standard,
+    /// The 108 tiles of three-player mahjong (sanma, 三麻), without 2m–8m.
+    Sanma,
+}
+
+impl TileSet {
+    /// Returns `true` if the tile type is used in the tile set.
+    ///
+    /// # Arguments
+    ///
+    /// * `tile` - Index of the tile type (0–33)
+    pub fn contains(&self, tile: usize) -> bool {
+        match self {
+            TileSet::Standard => tile < NUM_TILE_TYPE,
+            TileSet::Sanma => tile < NUM_TILE_TYPE && !SANMA_EXCLUDED_TILES.contains(&tile),
+        }
+    }
+}
+
+/// A declared meld (furo).
+///
+/// Each variant holds the index of the lowest tile in the meld.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Meld {
+    /// Sequence called from another player (e.g. `123m`)
+    Chi(usize),
+    /// Triplet called from another player (e.g. `111m`)
+    Pon(usize),
+    /// Quad formed with a tile from another player (e.g. `1111m`)
+    OpenKan(usize),
+    /// Quad declared from the player's own tiles (e.g. `1111m`)
+    ClosedKan(usize),
+}
+
+impl Meld {
+    /// Returns `true` if the meld is a chi, a pon or an open kan.
+    pub fn is_open(&self) -> bool {
+        !matches!(self, Meld::ClosedKan(_))
+    }
+
+    /// Returns the indices of the tiles in the meld in ascending order.
+    pub fn tiles(&self) -> Vec<usize> {
+        match *self {
+            Meld::Chi(i) => vec![i, i + 1, i + 2],
+            Meld::Pon(i) => vec![i; 3],
+            Meld::OpenKan(i) | Meld::ClosedKan(i) => vec![i; 4],
+        }
+    }
+
+    fn is_valid(&self, tile_set: TileSet) -> bool {
+        match *self {
+            // 1-7 of a suit, and sanma has no chi
+            Meld::Chi(i) => tile_set == TileSet::Standard && i < 27 && i % 9 < 7,
+            Meld::Pon(i) | Meld::OpenKan(i) | Meld::ClosedKan(i) => tile_set.contains(i),
+        }
+    }
+}
+
+/// Formats the meld in Tenhou-style notation (e.g. `123m`).
+///
+/// A closed kan is enclosed in brackets (e.g. `[1111z]`).
+impl fmt::Display for Meld {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut counts = [0; NUM_TILE_TYPE];
+        for i in self.tiles() {
+            counts[i] += 1;
+        }
+        match self {
+            Meld::ClosedKan(_) => write!(f, "[{}]", to_code(&counts)),
+            _ => write!(f, "{}", to_code(&counts)),
+        }
+    }
+}
+
+/// Parses a meld in the notation of its [Display](fmt::Display) implementation.
+///
+/// A chi is three consecutive tiles of a suit, a pon three copies of a tile, and an open kan
+/// four copies of a tile. A closed kan is four copies of a tile enclosed in brackets.
+impl FromStr for Meld {
+    type Err = HandParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || HandParseError::InvalidMeldCode(s.to_string());
+        let (code, is_closed) = match s.strip_prefix('[').and_then(|s| s.strip_suffix(']')) {
+            Some(code) => (code, true),
+            None => (s, false),
+        };
+        let counts = parse_hand(code).map_err(HandParseError::InvalidCode)?.tiles;
+        let tiles: Vec<usize> = (0..NUM_TILE_TYPE).filter(|&i| counts[i] > 0).collect();
+
+        let meld = match (tiles.as_slice(), is_closed) {
+            (&[i], true) if counts[i] == 4 => Meld::ClosedKan(i),
+            (&[i], false) if counts[i] == 4 => Meld::OpenKan(i),
+            (&[i], false) if counts[i] == 3 => Meld::Pon(i),
+            (&[i, j, k], false) if j == i + 1 && k == i + 2 => Meld::Chi(i),
+            _ => return Err(invalid()),
+        };
+        if !meld.is_valid(TileSet::Standard) {
+            return Err(invalid());
+        }
+
+        Ok(meld)
+    }
+}
+
+/// Error returned when a hand is not well-formed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HandError {
+    /// The meld does not exist (e.g. a chi of honor tiles).
+    InvalidMeld(Meld),
+    /// The hand has more than 4 melds.
+    TooManyMelds(usize),
+    /// The hand holds more than 4 copies of the tile type, including the melds.
+    TooManyCopies(usize),
+    /// The number of tiles is not between 1 and 14.
+    InvalidHandSize(usize),
+    /// The number of tiles is a multiple of 3, i.e. neither 3n+1 nor 3n+2.
+    InvalidHandShape(usize),
+    /// The number of concealed tiles does not match the number of melds.
+    InvalidNumTiles(usize),
+    /// The hand holds a tile type that is not used in the tile set (e.g. 2m in sanma).
+    NotInTileSet(usize),
+}
+
+impl fmt::Display for HandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HandError::InvalidMeld(meld) => write!(f, "invalid meld: {meld:?}"),
+            HandError::TooManyMelds(n) => {
+                write!(
+                    f,
+                    "too many melds: expected at most {MAX_NUM_MELD}, got {n}"
+                )
+            }
+            HandError::TooManyCopies(i) => write!(
+                f,
+                "too many copies of tile {i}: expected at most {MAX_NUM_TILE}"
+            ),
+            HandError::InvalidHandSize(n) => write!(
+                f,
+                "invalid hand size: expected {MIN_HAND_SIZE} to {MAX_HAND_SIZE} tiles, got {n}"
+            ),
+            HandError::InvalidHandShape(n) => {
+                write!(
+                    f,
+                    "invalid hand shape: expected 3n+1 or 3n+2 tiles, got {n}"
+                )
+            }
+            HandError::InvalidNumTiles(n) => {
+                write!(f, "invalid number of concealed tiles: {n}")
+            }
+            HandError::NotInTileSet(i) => write!(f, "tile {i} is not used in the tile set"),
+        }
+    }
+}
+
+impl std::error::Error for HandError {}
+
+/// Error returned when a string cannot be parsed into a [Hand] or a [Meld].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HandParseError {
+    /// A part of the string is not a valid Tenhou-style hand string.
+    ///
+    /// The index of the error is counted from the start of the part.
+    InvalidCode(ParseError),
+    /// A part after the concealed tiles is not a meld (e.g. `124m`).
+    InvalidMeldCode(String),
+    /// The hand is not well-formed.
+    InvalidHand(HandError),
+}
+
+impl fmt::Display for HandParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HandParseError::InvalidCode(e) => write!(f, "invalid hand string: {e}"),
+            HandParseError::InvalidMeldCode(code) => write!(f, "invalid meld: {code:?}"),
+            HandParseError::InvalidHand(e) => write!(f, "invalid hand: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for HandParseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            HandParseError::InvalidCode(e) => Some(e),
+            HandParseError::InvalidMeldCode(_) => None,
+            HandParseError::InvalidHand(e) => Some(e),
+        }
+    }
+}
+
+/// Validates that a [TileCounts] struct represents a well-formed hand.
+///
+/// A well-formed hand holds at most 4 copies of each tile type,
+/// and 3n+1 or 3n+2 tiles in total, between 1 and 14.
+///
+/// # Arguments
+///
+/// * `hand` - Reference to a [TileCounts] struct representing the hand's tile counts
+///
+/// # Returns
+///
+/// `Ok(())`, or a [HandError] describing the first problem found.
+pub fn validate_tile_counts(hand: &TileCounts) -> Result<(), HandError> {
+    if let Some(i) = hand.iter().position(|&c| c as usize > MAX_NUM_TILE) {
+        return Err(HandError::TooManyCopies(i));
+    }
+
+    let num_tiles = hand.iter().map(|&c| c as usize).sum::<usize>();
+    if !(MIN_HAND_SIZE..=MAX_HAND_SIZE).contains(&num_tiles) {
+        return Err(HandError::InvalidHandSize(num_tiles));
+    }
+    if num_tiles.is_multiple_of(3) {
+        return Err(HandError::InvalidHandShape(num_tiles));
+    }
+
+    Ok(())
+}
+
+/// Validates that a [TileCounts] struct represents a well-formed hand of a tile set.
+///
+/// In addition to the conditions of [validate_tile_counts],
+/// the hand must not hold tile types outside the tile set.
+///
+/// # Arguments
+///
+/// * `hand` - Reference to a [TileCounts] struct representing the hand's tile counts
+/// * `tile_set` - The tile set of the game
+///
+/// # Returns
+///
+/// `Ok(())`, or a [HandError] describing the first problem found.
+pub fn validate_tile_counts_in(hand: &TileCounts, tile_set: TileSet) -> Result<(), HandError> {
+    validate_tile_counts(hand)?;
+    if let Some(i) = (0..NUM_TILE_TYPE).find(|&i| hand[i] > 0 && !tile_set.contains(i)) {
+        return Err(HandError::NotInTileSet(i));
+    }
+
+    Ok(())
+}
+
+/// Tile counts that have been validated as a well-formed hand.
+///
+/// Use [`ShantenCalculator::try_calculate_shanten`](crate::ShantenCalculator::try_calculate_shanten)
+/// to validate a hand and calculate its shanten number at once.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ValidatedTileCounts(TileCounts);
+
+impl ValidatedTileCounts {
+    /// Creates a new instance after validating the tile counts with [validate_tile_counts].
+    ///
+    /// # Arguments
+    ///
+    /// * `hand` - A [TileCounts] struct representing the hand's tile counts
+    ///
+    /// # Returns
+    ///
+    /// The validated tile counts, or a [HandError] if they are not a well-formed hand.
+    pub fn new(hand: TileCounts) -> Result<Self, HandError> {
+        validate_tile_counts(&hand)?;
+        Ok(ValidatedTileCounts(hand))
+    }
+
+    /// Creates a new instance after validating the tile counts of a tile set
+    /// with [validate_tile_counts_in].
+    ///
+    /// # Arguments
+    ///
+    /// * `hand` - A [TileCounts] struct representing the hand's tile counts
+    /// * `tile_set` - The tile set of the game
+    ///
+    /// # Returns
+    ///
+    /// The validated tile counts, or a [HandError] if they are not a well-formed hand.
+    pub fn new_in(hand: TileCounts, tile_set: TileSet) -> Result<Self, HandError> {
+        validate_tile_counts_in(&hand, tile_set)?;
+        Ok(ValidatedTileCounts(hand))
+    }
+
+    /// Returns the tile counts.
+    pub fn tile_counts(&self) -> &TileCounts {
+        &self.0
+    }
+}
+
+/// A hand consisting of concealed tiles and declared melds.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Hand {
+    concealed: TileCounts,
+    melds: Vec<Meld>,
+    tile_set: TileSet,
+}
+
+impl Hand {
+    /// Creates a new instance after validating the hand.
+    ///
+    /// # Arguments
+    ///
+    /// * `concealed` - A [TileCounts] struct representing the concealed tiles.
+    ///   The number of tiles must be 13 or 14 minus 3 for each meld.
+    /// * `melds` - The declared melds
+    ///
+    /// # Returns
+    ///
+    /// The hand, or a [HandError] if it is not well-formed.
+    pub fn new(concealed: TileCounts, melds: Vec<Meld>) -> Result<Self, HandError> {
+        Self::new_in(concealed, melds, TileSet::Standard)
+    }
+
+    /// Creates a new instance of a hand of a tile set after validating the hand.
+    ///
+    /// Melds must consist of tiles in the tile set, so no chi is valid in [TileSet::Sanma].
+    ///
+    /// # Arguments
+    ///
+    /// * `concealed` - A [TileCounts] struct representing the concealed tiles.
+    ///   The number of tiles must be 13 or 14 minus 3 for each meld.
+    /// * `melds` - The declared melds
+    /// * `tile_set` - The tile set of the game
+    ///
+    /// # Returns
+    ///
+    /// The hand, or a [HandError] if it is not well-formed.
+    pub fn new_in(
+        concealed: TileCounts,
+        melds: Vec<Meld>,
+        tile_set: TileSet,
+    ) -> Result<Self, HandError> {
+        validate_tile_counts_in(&concealed, tile_set)?;
+        if melds.len() > MAX_NUM_MELD {
+            return Err(HandError::TooManyMelds(melds.len()));
+        }
+
+        let mut all = concealed;
+        for meld in &melds {
+            if !meld.is_valid(tile_set) {
+                return Err(HandError::InvalidMeld(*meld));
+            }
+            for i in meld.tiles() {
+                all[i] += 1;
+            }
+        }
+        if let Some(i) = all.iter().position(|&c| c as usize > MAX_NUM_TILE) {
+            return Err(HandError::TooManyCopies(i));
+        }
+
+        let num_tiles = concealed.iter().map(|&c| c as usize).sum::<usize>();
+        let num_closed_tiles = num_tiles + melds.len() * 3;
+        if num_closed_tiles != MAX_HAND_SIZE - 1 && num_closed_tiles != MAX_HAND_SIZE {
+            return Err(HandError::InvalidNumTiles(num_tiles));
+        }
+
+        Ok(Hand {
+            concealed,
+            melds,
+            tile_set,
+        })
+    }
+
+    /// Returns the concealed tiles.
+    pub fn concealed(&self) -> &TileCounts {
+        &self.concealed
+    }
+
+    /// Returns the declared melds.
+    pub fn melds(&self) -> &[Meld] {
+        &self.melds
+    }
+
+    /// Returns the tile set of the game.
+    pub fn tile_set(&self) -> TileSet {
+        self.tile_set
+    }
+
+    /// Returns `true` if the hand has no open melds.
+    ///
+    /// Closed kans do not open the hand.
+    pub fn is_closed(&self) -> bool {
+        !self.melds.iter().any(Meld::is_open)
+    }
+}
+
+/// Formats the hand as the concealed tiles followed by the melds, separated by spaces
+/// (e.g. `456p789s1z 123m [7777z]`).
+///
+/// See [to_code] and the [Display](fmt::Display) implementation of [Meld] for the notation.
+/// The result can be parsed back with [str::parse].
+impl fmt::Display for Hand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", to_code(&self.concealed))?;
+        for meld in &self.melds {
+            write!(f, " {meld}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Parses a hand in the notation of its [Display](fmt::Display) implementation
+/// (e.g. `456p789s1z 123m [7777z]`).
+///
+/// The first part is the concealed tiles, and each following part separated by whitespace
+/// is a [Meld]. The hand is validated as a hand of [TileSet::Standard]; for other tile sets,
+/// parse the parts separately and use [Hand::new_in].
+impl FromStr for Hand {
+    type Err = HandParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.split_whitespace();
+        let concealed = parse_hand(parts.next().unwrap_or_default())
+            .map_err(HandParseError::InvalidCode)?
+            .tiles;
+        let melds = parts.map(str::parse).collect::<Result<Vec<Meld>, _>>()?;
+        Hand::new(concealed, melds).map_err(HandParseError::InvalidHand)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TileCountsExt;
+
+    #[test]
+    fn test_validate_tile_counts() {
+        assert_eq!(validate_tile_counts(&TileCounts::from_code("1z")), Ok(()));
+        assert_eq!(
+            validate_tile_counts(&TileCounts::from_code("11112222333344z")),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn test_validate_tile_counts_too_many_copies() {
+        let mut hand = TileCounts::from_code("1111m");
+        hand[0] = 5;
+        assert_eq!(
+            validate_tile_counts(&hand),
+            Err(HandError::TooManyCopies(0))
+        );
+    }
+
+    #[test]
+    fn test_validate_tile_counts_invalid_size() {
+        assert_eq!(
+            validate_tile_counts(&[0; NUM_TILE_TYPE]),
+            Err(HandError::InvalidHandSize(0))
+        );
+        assert_eq!(
+            validate_tile_counts(&TileCounts::from_code("123456789m123456p")),
+            Err(HandError::InvalidHandSize(15))
+        );
+    }
+
+    #[test]
+    fn test_validate_tile_counts_invalid_shape() {
+        assert_eq!(
+            validate_tile_counts(&TileCounts::from_code("123m456p")),
+            Err(HandError::InvalidHandShape(6))
+        );
+    }
+
+    #[test]
+    fn test_validate_tile_counts_in_sanma() {
+        let hand = TileCounts::from_code("19m19p19s1234567z");
+        assert_eq!(validate_tile_counts_in(&hand, TileSet::Sanma), Ok(()));
+
+        let hand = TileCounts::from_code("129m19p19s123456z");
+        assert_eq!(validate_tile_counts_in(&hand, TileSet::Standard), Ok(()));
+        assert_eq!(
+            validate_tile_counts_in(&hand, TileSet::Sanma),
+            Err(HandError::NotInTileSet(1))
+        );
+    }
+
+    #[test]
+    fn test_validated_tile_counts() {
+        let hand = TileCounts::from_code("123m456p789s1122z");
+        assert_eq!(ValidatedTileCounts::new(hand).unwrap().tile_counts(), &hand);
+        assert_eq!(
+            ValidatedTileCounts::new(TileCounts::from_code("123m456p789s112223z")),
+            Err(HandError::InvalidHandSize(15))
+        );
+
+        let hand = TileCounts::from_code("19m456p789s11223z");
+        assert_eq!(
+            ValidatedTileCounts::new_in(hand, TileSet::Sanma)
+                .unwrap()
+                .tile_counts(),
+            &hand
+        );
+        assert_eq!(
+            ValidatedTileCounts::new_in(TileCounts::from_code("23m456p789s11223z"), TileSet::Sanma),
+            Err(HandError::NotInTileSet(1))
+        );
+    }
+
+    #[test]
+    fn test_new_closed() {
+        let hand = Hand::new(TileCounts::from_code("123m456p789s1122z"), vec![]).unwrap();
+        assert!(hand.is_closed());
+        assert!(hand.melds().is_empty());
+    }
+
+    #[test]
+    fn test_new_with_melds() {
+        let concealed = TileCounts::from_code("456p789s1z");
+        let melds = vec![Meld::Chi(0), Meld::ClosedKan(33)];
+        let hand = Hand::new(concealed, melds.clone()).unwrap();
+        assert_eq!(hand.concealed(), &concealed);
+        assert_eq!(hand.melds(), melds);
+        assert!(!hand.is_closed());
+        assert_eq!(hand.to_string(), "456p789s1z 123m [7777z]");
+
+        let melds = [0, 9, 18, 27].map(Meld::ClosedKan).to_vec();
+        let hand = Hand::new(TileCounts::from_code("7z"), melds).unwrap();
+        assert!(hand.is_closed());
+    }
+
+    #[test]
+    fn test_parse_meld() {
+        assert_eq!("123m".parse(), Ok(Meld::Chi(0)));
+        assert_eq!("789s".parse(), Ok(Meld::Chi(24)));
+        assert_eq!("550p".parse(), Ok(Meld::Pon(13)));
+        assert_eq!("1111z".parse(), Ok(Meld::OpenKan(27)));
+        assert_eq!("[7777z]".parse(), Ok(Meld::ClosedKan(33)));
+
+        for code in ["124m", "123z", "89m1p", "11m", "[111z]", "[123m]"] {
+            assert_eq!(
+                code.parse::<Meld>(),
+                Err(HandParseError::InvalidMeldCode(code.to_string()))
+            );
+        }
+        for code in ["12x", "[1111z"] {
+            assert!(matches!(
+                code.parse::<Meld>(),
+                Err(HandParseError::InvalidCode(_))
+            ));
+        }
+    }
+
+    #[test]
+    fn test_parse_hand_round_trip() {
+        for (code, melds) in [
+            ("123m456p789s1122z", vec![]),
+            ("456p789s1z", vec![Meld::Chi(0), Meld::ClosedKan(33)]),
+            (
+                "789s12z",
+                vec![Meld::Pon(9), Meld::OpenKan(18), Meld::Chi(22)],
+            ),
+            ("7z", [0, 9, 18, 27].map(Meld::ClosedKan).to_vec()),
+        ] {
+            let concealed = TileCounts::from_code(code);
+            let hand = Hand::new(concealed, melds).unwrap();
+            assert_eq!(hand.to_string().parse(), Ok(hand));
+        }
+    }
+
+    #[test]
+    fn test_parse_hand_invalid() {
+        assert!(matches!(
+            "456p789s1z 123m [7777x]".parse::<Hand>(),
+            Err(HandParseError::InvalidCode(_))
+        ));
+        assert_eq!(
+            "456p789s1z 123m 7777".parse::<Hand>(),
+            Err(HandParseError::InvalidCode(ParseError::MissingType {
+                index: 0
+            }))
+        );
+        assert_eq!(
+            "456p789s1z 124m [7777z]".parse::<Hand>(),
+            Err(HandParseError::InvalidMeldCode("124m".to_string()))
+        );
+        assert_eq!(
+            "456p789s1z 123m".parse::<Hand>(),
+            Err(HandParseError::InvalidHand(HandError::InvalidNumTiles(7)))
+        );
+        assert_eq!(
+            "".parse::<Hand>(),
+            Err(HandParseError::InvalidHand(HandError::InvalidHandSize(0)))
+        );
+    }
+
+    #[test]
+    fn test_new_invalid_meld() {
+        let concealed = TileCounts::from_code("456p789s1122z");
+        assert_eq!(
+            Hand::new(concealed, vec![Meld::Chi(7)]),
+            Err(HandError::InvalidMeld(Meld::Chi(7)))
+        );
+        assert_eq!(
+            Hand::new(concealed, vec![Meld::Chi(27)]),
+            Err(HandError::InvalidMeld(Meld::Chi(27)))
+        );
+        assert_eq!(
+            Hand::new(concealed, vec![Meld::Pon(34)]),
+            Err(HandError::InvalidMeld(Meld::Pon(34)))
+        );
+    }
+
+    #[test]
+    fn test_new_in_sanma() {
+        let concealed = TileCounts::from_code("456p789s1z");
+        let hand = Hand::new_in(
+            concealed,
+            vec![Meld::Pon(8), Meld::ClosedKan(33)],
+            TileSet::Sanma,
+        )
+        .unwrap();
+        assert_eq!(hand.tile_set(), TileSet::Sanma);
+
+        assert_eq!(
+            Hand::new_in(
+                concealed,
+                vec![Meld::Chi(9), Meld::ClosedKan(33)],
+                TileSet::Sanma
+            ),
+            Err(HandError::InvalidMeld(Meld::Chi(9)))
+        );
+        assert_eq!(
+            Hand::new_in(
+                concealed,
+                vec![Meld::Pon(4), Meld::ClosedKan(33)],
+                TileSet::Sanma
+            ),
+            Err(HandError::InvalidMeld(Meld::Pon(4)))
+        );
+    }
+
+    #[test]
+    fn test_new_too_many_melds() {
+        let melds = vec![Meld::Pon(27); 5];
+        assert_eq!(
+            Hand::new(TileCounts::from_code("1m"), melds),
+            Err(HandError::TooManyMelds(5))
+        );
+    }
+
+    #[test]
+    fn test_new_too_many_copies() {
+        let concealed = TileCounts::from_code("1m456p789s1122z");
+        assert_eq!(
+            Hand::new(concealed, vec![Meld::OpenKan(0)]),
+            Err(HandError::TooManyCopies(0))
+        );
+    }
+
+    #[test]
+    fn test_new_invalid_num_tiles() {
+        let concealed = TileCounts::from_code("123m456p789s1122z");
+        assert_eq!(
+            Hand::new(concealed, vec![Meld::Pon(31)]),
+            Err(HandError::InvalidNumTiles(13))
+        );
+    }
+}
@@ -0,0 +1,515 @@
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader};
+use std::path::{Path, PathBuf};
+
+use crate::constants::{MAX_NUM_TILE, NUM_TILE_TYPE};
+use crate::hand::{HandError, validate_tile_counts};
+use crate::tenhou::{ParseError, parse_hand};
+use crate::types::TileCounts;
+
+/// Directory of the resource files generated by `handgen`.
+///
+/// It is resolved when `common` is compiled, so it does not depend on the working directory
+/// or on the location of the calling crate.
+pub const RESOURCES_DIR: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/../resources");
+
+/// Character that starts a comment in a hand file.
+const COMMENT: char = '#';
+
+/// Returns the path of a file in [RESOURCES_DIR].
+///
+/// # Arguments
+///
+/// * `filename` - Name of the file (e.g. `"hands_normal_10000.txt"`)
+pub fn resource_path(filename: &str) -> PathBuf {
+    Path::new(RESOURCES_DIR).join(filename)
+}
+
+/// Error returned when a line of a hand file is not a valid hand.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LineError {
+    /// A token of a tile index list is not a tile index (0–33).
+    InvalidTileIndex(String),
+    /// The line is not a valid Tenhou-style hand string.
+    InvalidCode(ParseError),
+    /// The hand is not well-formed.
+    InvalidHand(HandError),
+    /// A shanten number after the hand is not an integer.
+    InvalidShanten(String),
+    /// The line does not have the expected number of shanten numbers after the hand.
+    InvalidNumShantens {
+        /// Expected number of shanten numbers
+        expected: usize,
+        /// Actual number of shanten numbers
+        actual: usize,
+    },
+}
+
+impl fmt::Display for LineError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LineError::InvalidTileIndex(token) => {
+                write!(f, "invalid tile index: {token:?}")
+            }
+            LineError::InvalidCode(e) => write!(f, "invalid hand string: {e}"),
+            LineError::InvalidHand(e) => write!(f, "invalid hand: {e}"),
+            LineError::InvalidShanten(token) => {
+                write!(f, "invalid shanten number: {token:?}")
+            }
+            LineError::InvalidNumShantens { expected, actual } => write!(
+                f,
+                "invalid number of shanten numbers: expected {expected}, got {actual}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LineError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            LineError::InvalidTileIndex(_)
+            | LineError::InvalidShanten(_)
+            | LineError::InvalidNumShantens { .. } => None,
+            LineError::InvalidCode(e) => Some(e),
+            LineError::InvalidHand(e) => Some(e),
+        }
+    }
+}
+
+/// Error returned when a hand file cannot be read.
+#[derive(Debug)]
+pub enum HandFileError {
+    /// The file cannot be opened or read.
+    Io(io::Error),
+    /// A line is not a valid hand.
+    InvalidLine {
+        /// Line number, counted from 1
+        line_number: usize,
+        /// The problem found on the line
+        error: LineError,
+    },
+}
+
+impl fmt::Display for HandFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HandFileError::Io(e) => write!(f, "cannot read hand file: {e}"),
+            HandFileError::InvalidLine { line_number, error } => {
+                write!(f, "line {line_number}: {error}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for HandFileError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            HandFileError::Io(e) => Some(e),
+            HandFileError::InvalidLine { error, .. } => Some(error),
+        }
+    }
+}
+
+impl From<io::Error> for HandFileError {
+    fn from(e: io::Error) -> Self {
+        HandFileError::Io(e)
+    }
+}
+
+/// Hand of an annotated hand file with its shanten numbers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AnnotatedHand {
+    /// Tile counts of the hand
+    pub tiles: TileCounts,
+    /// Shanten number of the general form
+    pub general: i8,
+    /// Shanten number of Seven Pairs
+    pub seven_pairs: i8,
+    /// Shanten number of Thirteen Orphans
+    pub thirteen_orphans: i8,
+}
+
+fn strip_comment(line: &str) -> &str {
+    match line.split_once(COMMENT) {
+        Some((content, _)) => content,
+        None => line,
+    }
+    .trim()
+}
+
+fn parse_tile_indices(line: &str) -> Result<TileCounts, LineError> {
+    let mut hand = [0; NUM_TILE_TYPE];
+    for token in line.split_whitespace() {
+        let tile = token
+            .parse::<usize>()
+            .ok()
+            .filter(|&tile| tile < NUM_TILE_TYPE)
+            .ok_or_else(|| LineError::InvalidTileIndex(token.to_string()))?;
+        if hand[tile] as usize >= MAX_NUM_TILE {
+            return Err(LineError::InvalidHand(HandError::TooManyCopies(tile)));
+        }
+        hand[tile] += 1;
+    }
+    Ok(hand)
+}
+
+/// Parses a line of a hand file.
+///
+/// A line holds either tile indices (0–33) separated by whitespace (e.g. `0 1 2 9 9`),
+/// or a Tenhou-style hand string (e.g. `123m11p`).
+/// Everything after `#` is a comment.
+///
+/// # Arguments
+///
+/// * `line` - A line of a hand file
+///
+/// # Returns
+///
+/// The hand, `None` if the line is blank or only a comment,
+/// or a [LineError] if the line is not a well-formed hand
+/// (see [`validate_tile_counts`](crate::validate_tile_counts)).
+pub fn parse_hand_line(line: &str) -> Result<Option<TileCounts>, LineError> {
+    let line = strip_comment(line);
+    if line.is_empty() {
+        return Ok(None);
+    }
+    parse_hand_part(line).map(Some)
+}
+
+fn parse_hand_part(hand: &str) -> Result<TileCounts, LineError> {
+    let hand = if hand.chars().any(|c| c.is_ascii_alphabetic()) {
+        parse_hand(hand).map_err(LineError::InvalidCode)?.tiles
+    } else {
+        parse_tile_indices(hand)?
+    };
+    validate_tile_counts(&hand).map_err(LineError::InvalidHand)?;
+    Ok(hand)
+}
+
+fn parse_shantens<const N: usize>(shantens: &str) -> Result<[i8; N], LineError> {
+    let shantens = shantens
+        .split_whitespace()
+        .map(|token| {
+            token
+                .parse()
+                .map_err(|_| LineError::InvalidShanten(token.to_string()))
+        })
+        .collect::<Result<Vec<i8>, _>>()?;
+    let actual = shantens.len();
+    shantens
+        .try_into()
+        .map_err(|_| LineError::InvalidNumShantens {
+            expected: N,
+            actual,
+        })
+}
+
+/// Parses a line of an annotated hand file, written by `handgen --annotate`.
+///
+/// A line holds a hand in the format of [parse_hand_line], `|`, and its general form,
+/// Seven Pairs and Thirteen Orphans shanten numbers (e.g. `0 1 2 9 9 | 0 4 10`).
+///
+/// # Arguments
+///
+/// * `line` - A line of an annotated hand file
+///
+/// # Returns
+///
+/// The [AnnotatedHand], `None` if the line is blank or only a comment,
+/// or a [LineError] if the line is malformed.
+pub fn parse_annotated_line(line: &str) -> Result<Option<AnnotatedHand>, LineError> {
+    let line = strip_comment(line);
+    if line.is_empty() {
+        return Ok(None);
+    }
+
+    let (hand, shantens) = line.split_once('|').unwrap_or((line, ""));
+    let [general, seven_pairs, thirteen_orphans] = parse_shantens(shantens)?;
+    Ok(Some(AnnotatedHand {
+        tiles: parse_hand_part(hand.trim())?,
+        general,
+        seven_pairs,
+        thirteen_orphans,
+    }))
+}
+
+/// Parses a line of the single-suit hand file (`hands_single_suit_all.txt`).
+///
+/// A line holds a hand in Tenhou notation and its shanten number, separated by whitespace
+/// (e.g. `1123m 0`).
+///
+/// # Arguments
+///
+/// * `line` - A line of the single-suit hand file
+///
+/// # Returns
+///
+/// The hand and its shanten number, `None` if the line is blank or only a comment,
+/// or a [LineError] if the line is malformed.
+pub fn parse_single_suit_line(line: &str) -> Result<Option<(TileCounts, i8)>, LineError> {
+    let line = strip_comment(line);
+    if line.is_empty() {
+        return Ok(None);
+    }
+
+    // A hand has no whitespace, so everything after it is taken as shanten numbers
+    let (hand, shanten) = line.split_once(char::is_whitespace).unwrap_or((line, ""));
+    let [shanten] = parse_shantens(shanten)?;
+    Ok(Some((parse_hand_part(hand)?, shanten)))
+}
+
+/// Reads the lines of a hand file with a line parser, skipping the lines parsed as `None`.
+///
+/// This is the common part of [read_hands] and the loaders of the other hand file formats,
+/// and can be used for other formats in the same way.
+///
+/// # Arguments
+///
+/// * `reader` - The reader of a hand file
+/// * `parse_line` - The parser of a line, such as [parse_hand_line]
+///
+/// # Returns
+///
+/// The parsed lines in order,
+/// or a [HandFileError] with the number of the first invalid line.
+pub fn read_lines<R: BufRead, T>(
+    reader: R,
+    parse_line: impl Fn(&str) -> Result<Option<T>, LineError>,
+) -> Result<Vec<T>, HandFileError> {
+    let mut items = Vec::new();
+    for (i, line) in reader.lines().enumerate() {
+        let line_number = i + 1;
+        match parse_line(&line?) {
+            Ok(Some(item)) => items.push(item),
+            Ok(None) => {}
+            Err(error) => return Err(HandFileError::InvalidLine { line_number, error }),
+        }
+    }
+    Ok(items)
+}
+
+/// Reads hands from a reader, one hand per line.
+///
+/// See [parse_hand_line] for the format of each line.
+///
+/// # Arguments
+///
+/// * `reader` - The reader of a hand file
+///
+/// # Returns
+///
+/// The hands in the order of the lines,
+/// or a [HandFileError] with the number of the first invalid line.
+pub fn read_hands<R: BufRead>(reader: R) -> Result<Vec<TileCounts>, HandFileError> {
+    read_lines(reader, parse_hand_line)
+}
+
+/// Reads the hands of a file, one hand per line.
+///
+/// See [parse_hand_line] for the format of each line.
+/// Use [resource_path] for the files in `resources/`.
+///
+/// # Arguments
+///
+/// * `path` - Path of the hand file
+///
+/// # Returns
+///
+/// The hands in the order of the lines,
+/// or a [HandFileError] if the file cannot be read or has an invalid line.
+pub fn load_hands<P: AsRef<Path>>(path: P) -> Result<Vec<TileCounts>, HandFileError> {
+    let file = File::open(path)?;
+    read_hands(BufReader::new(file))
+}
+
+/// Reads the hands of an annotated hand file with their shanten numbers.
+///
+/// See [parse_annotated_line] for the format of each line.
+///
+/// # Arguments
+///
+/// * `path` - Path of the annotated hand file
+///
+/// # Returns
+///
+/// The [AnnotatedHand]s in the order of the lines,
+/// or a [HandFileError] if the file cannot be read or has an invalid line.
+pub fn load_annotated_hands<P: AsRef<Path>>(path: P) -> Result<Vec<AnnotatedHand>, HandFileError> {
+    let file = File::open(path)?;
+    read_lines(BufReader::new(file), parse_annotated_line)
+}
+
+/// Reads the hands of the single-suit hand file with their shanten numbers.
+///
+/// See [parse_single_suit_line] for the format of each line.
+///
+/// # Arguments
+///
+/// * `path` - Path of the single-suit hand file
+///
+/// # Returns
+///
+/// The hands and their shanten numbers in the order of the lines,
+/// or a [HandFileError] if the file cannot be read or has an invalid line.
+pub fn load_single_suit_hands<P: AsRef<Path>>(
+    path: P,
+) -> Result<Vec<(TileCounts, i8)>, HandFileError> {
+    let file = File::open(path)?;
+    read_lines(BufReader::new(file), parse_single_suit_line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TileCountsExt;
+
+    #[test]
+    fn test_parse_tile_indices() {
+        assert_eq!(
+            parse_hand_line("0 1 2 9 9"),
+            Ok(Some(TileCounts::from_code("123m11p")))
+        );
+    }
+
+    #[test]
+    fn test_parse_code() {
+        assert_eq!(
+            parse_hand_line("  123m11p  "),
+            Ok(Some(TileCounts::from_code("123m11p")))
+        );
+    }
+
+    #[test]
+    fn test_parse_comments() {
+        assert_eq!(parse_hand_line(""), Ok(None));
+        assert_eq!(parse_hand_line("# 0 1 2 9 9"), Ok(None));
+        assert_eq!(
+            parse_hand_line("123m11p # tenpai"),
+            Ok(Some(TileCounts::from_code("123m11p")))
+        );
+    }
+
+    #[test]
+    fn test_parse_invalid_lines() {
+        assert_eq!(
+            parse_hand_line("0 1 2 9 34"),
+            Err(LineError::InvalidTileIndex("34".to_string()))
+        );
+        assert_eq!(
+            parse_hand_line("0 1 2 9 -1"),
+            Err(LineError::InvalidTileIndex("-1".to_string()))
+        );
+        assert_eq!(
+            parse_hand_line("0 0 0 0 0"),
+            Err(LineError::InvalidHand(HandError::TooManyCopies(0)))
+        );
+        assert_eq!(
+            parse_hand_line("0 1 2"),
+            Err(LineError::InvalidHand(HandError::InvalidHandShape(3)))
+        );
+        assert!(matches!(
+            parse_hand_line("123m11x"),
+            Err(LineError::InvalidCode(ParseError::InvalidCharacter { .. }))
+        ));
+    }
+
+    #[test]
+    fn test_read_hands() {
+        let file = "# hands\n0 1 2 9 9\n\n123m11p\n";
+        let hands = read_hands(file.as_bytes()).unwrap();
+        assert_eq!(hands, vec![TileCounts::from_code("123m11p"); 2]);
+    }
+
+    #[test]
+    fn test_read_hands_line_number() {
+        let file = "# hands\n0 1 2 9 9\n\n0 1 2\n";
+        match read_hands(file.as_bytes()) {
+            Err(HandFileError::InvalidLine { line_number, error }) => {
+                assert_eq!(line_number, 4);
+                assert_eq!(
+                    error,
+                    LineError::InvalidHand(HandError::InvalidHandShape(3))
+                );
+            }
+            result => panic!("unexpected result: {result:?}"),
+        }
+    }
+
+    #[test]
+    fn test_parse_annotated_line() {
+        assert_eq!(
+            parse_annotated_line("0 1 2 9 9 | 0 4 10 # comment"),
+            Ok(Some(AnnotatedHand {
+                tiles: TileCounts::from_code("123m11p"),
+                general: 0,
+                seven_pairs: 4,
+                thirteen_orphans: 10,
+            }))
+        );
+        assert_eq!(parse_annotated_line("# 0 1 2 9 9 | 0 4 10"), Ok(None));
+        assert_eq!(
+            parse_annotated_line("0 1 2 9 9"),
+            Err(LineError::InvalidNumShantens {
+                expected: 3,
+                actual: 0
+            })
+        );
+        assert_eq!(
+            parse_annotated_line("0 1 2 9 9 | 0 4 x"),
+            Err(LineError::InvalidShanten("x".to_string()))
+        );
+        assert_eq!(
+            parse_annotated_line("0 1 2 9 34 | 0 4 10"),
+            Err(LineError::InvalidTileIndex("34".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_parse_single_suit_line() {
+        assert_eq!(
+            parse_single_suit_line("123m11p -1"),
+            Ok(Some((TileCounts::from_code("123m11p"), -1)))
+        );
+        assert_eq!(
+            parse_single_suit_line("123m11p"),
+            Err(LineError::InvalidNumShantens {
+                expected: 1,
+                actual: 0
+            })
+        );
+        assert_eq!(
+            parse_single_suit_line("123m11p 0 1"),
+            Err(LineError::InvalidNumShantens {
+                expected: 1,
+                actual: 2
+            })
+        );
+        assert_eq!(
+            parse_single_suit_line("123m11p\t 0  # comment"),
+            Ok(Some((TileCounts::from_code("123m11p"), 0)))
+        );
+        assert_eq!(
+            parse_single_suit_line("123m1x 0"),
+            Err(LineError::InvalidCode(ParseError::InvalidCharacter {
+                index: 5,
+                character: 'x'
+            }))
+        );
+    }
+
+    #[test]
+    fn test_load_resource_hands() {
+        let hands = load_hands(resource_path("hands_shanten_0_1000.txt")).unwrap();
+        assert_eq!(hands.len(), 1000);
+        let hands =
+            load_annotated_hands(resource_path("hands_normal_annotated_10000.txt")).unwrap();
+        assert_eq!(hands.len(), 10_000);
+        let hands = load_single_suit_hands(resource_path("hands_single_suit_all.txt")).unwrap();
+        assert!(!hands.is_empty());
+        assert!(matches!(
+            load_hands(resource_path("no_such_file.txt")),
+            Err(HandFileError::Io(_))
+        ));
+    }
+}
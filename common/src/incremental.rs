@@ -0,0 +1,134 @@
+use crate::constants::{MAX_NUM_TILE, NUM_TILE_TYPE};
+use crate::traits::ShantenCalculator;
+use crate::types::TileCounts;
+
+/// Number of groups of tiles: the three suits and the honors.
+pub const NUM_GROUP: usize = 4;
+
+/// Returns the group of a tile type: 0 (m), 1 (p), 2 (s) or 3 (z).
+///
+/// # Arguments
+///
+/// * `tile` - Index of the tile type (0–33)
+pub fn group_of(tile: usize) -> usize {
+    tile / 9
+}
+
+/// Trait for shanten number calculation algorithms that process each group of tiles
+/// (the three suits and the honors) independently and combine the results.
+///
+/// Implement this trait in addition to [ShantenCalculator] to use [IncrementalCalculator].
+pub trait GroupCalculator: ShantenCalculator {
+    /// Result of a single group, cached by [IncrementalCalculator].
+    type GroupResult: Clone;
+
+    /// Calculates the result of a group.
+    ///
+    /// # Arguments
+    ///
+    /// * `hand` - Reference to a [TileCounts] struct representing the hand's tile counts
+    /// * `group` - Index of the group (see [group_of])
+    ///
+    /// # Returns
+    ///
+    /// The result of the group. It must depend only on the tiles of the group.
+    fn calculate_group(&self, hand: &TileCounts, group: usize) -> Self::GroupResult;
+
+    /// Calculates the shanten number from the results of all groups.
+    ///
+    /// # Arguments
+    ///
+    /// * `groups` - The results of the groups
+    /// * `num_tiles` - The number of tiles in the hand
+    ///
+    /// # Returns
+    ///
+    /// The same shanten number as [`calculate_shanten`](ShantenCalculator::calculate_shanten).
+    fn calculate_shanten_from_groups(
+        &self,
+        groups: &[Self::GroupResult; NUM_GROUP],
+        num_tiles: u8,
+    ) -> i8;
+}
+
+/// Stateful calculator that updates the shanten number of a hand as tiles are drawn and discarded.
+///
+/// The result of each group is cached, so a draw or a discard only recalculates
+/// the group of the tile.
+pub struct IncrementalCalculator<'a, C: GroupCalculator> {
+    calculator: &'a C,
+    hand: TileCounts,
+    num_tiles: u8,
+    groups: [C::GroupResult; NUM_GROUP],
+}
+
+impl<'a, C: GroupCalculator> IncrementalCalculator<'a, C> {
+    /// Creates a new instance and calculates the results of all groups.
+    ///
+    /// # Arguments
+    ///
+    /// * `calculator` - The calculator used for each group
+    /// * `hand` - Reference to a [TileCounts] struct representing the initial hand
+    pub fn new(calculator: &'a C, hand: &TileCounts) -> Self {
+        IncrementalCalculator {
+            calculator,
+            hand: *hand,
+            num_tiles: hand.iter().sum(),
+            groups: std::array::from_fn(|group| calculator.calculate_group(hand, group)),
+        }
+    }
+
+    /// Returns the current hand.
+    pub fn hand(&self) -> &TileCounts {
+        &self.hand
+    }
+
+    /// Calculates the shanten number of the current hand from the cached results.
+    pub fn shanten(&self) -> i8 {
+        self.calculator
+            .calculate_shanten_from_groups(&self.groups, self.num_tiles)
+    }
+
+    /// Adds a tile to the hand and recalculates its group.
+    ///
+    /// # Arguments
+    ///
+    /// * `tile` - Index of the tile type (0–33)
+    ///
+    /// # Panics
+    ///
+    /// Panics if the hand already holds all 4 copies of the tile type.
+    pub fn draw(&mut self, tile: usize) {
+        assert!(
+            tile < NUM_TILE_TYPE && (self.hand[tile] as usize) < MAX_NUM_TILE,
+            "cannot draw tile {tile}"
+        );
+        self.hand[tile] += 1;
+        self.num_tiles += 1;
+        self.update(tile);
+    }
+
+    /// Removes a tile from the hand and recalculates its group.
+    ///
+    /// # Arguments
+    ///
+    /// * `tile` - Index of the tile type (0–33)
+    ///
+    /// # Panics
+    ///
+    /// Panics if the hand does not hold the tile type.
+    pub fn discard(&mut self, tile: usize) {
+        assert!(
+            tile < NUM_TILE_TYPE && self.hand[tile] > 0,
+            "cannot discard tile {tile}"
+        );
+        self.hand[tile] -= 1;
+        self.num_tiles -= 1;
+        self.update(tile);
+    }
+
+    fn update(&mut self, tile: usize) {
+        let group = group_of(tile);
+        self.groups[group] = self.calculator.calculate_group(&self.hand, group);
+    }
+}
@@ -8,6 +8,25 @@
 //! - Implement your own algorithm by implementing the [`ShantenCalculator`] trait.
 //! - Use the [`shanten_tests!`] macro to automatically generate standard test cases
 //!   for your implementation.
+//! - Use the [`annotated_tests!`] macro to check your implementation against the random hands
+//!   in `resources` annotated with their shanten numbers.
+//! - If you override [`ShantenCalculator::calculate_necessary_tiles`], use the
+//!   [`necessary_tiles_tests!`] macro to check it against the default implementation.
+//! - Use [`Hand`] and [`ShantenCalculator::calculate_shanten_with_melds`] for hands
+//!   with declared melds.
+//! - Implement [`GroupCalculator`] for algorithms that process each suit independently,
+//!   and use [`IncrementalCalculator`] to update the shanten number on each draw and discard.
+//! - Use [`ShantenCalculator::calculate_shanten_batch`] to evaluate many hands at once.
+//!   With the `rayon` feature, `calculate_shanten_batch_parallel` spreads them over threads.
+//! - Use [`ShantenCalculator::try_calculate_shanten`] or [`ValidatedTileCounts`] to reject
+//!   hands that are not well-formed, such as hands with more than 14 tiles.
+//! - Use [`TileSet::Sanma`] with [`ValidatedTileCounts::new_in`] and [`Hand::new_in`] for
+//!   three-player mahjong hands without 2m–8m.
+//! - Use [`parse_hand`] to parse Tenhou-style hand strings from users.
+//!   Hands with melds, as formatted by [`Hand`], can be parsed back with [`str::parse`].
+//! - Use [`load_hands`] and [`resource_path`] to read hand files, such as those in `resources`.
+//! - Use the [`shanten_benches!`] macro for benchmarks on nightly Rust, or `criterion_benches!`
+//!   (with the `criterion` feature) for benchmarks on stable Rust.
 //! - See the `dummy` crate for a minimal example.
 //!
 //! Most users only need to implement [`ShantenCalculator`] and
@@ -19,12 +38,30 @@
 
 mod bench_macros;
 mod constants;
+mod decomposition;
+mod discards;
+mod hand;
+mod hand_file;
+mod incremental;
+mod necessary_tiles;
+mod tenhou;
 mod test_macros;
 mod test_utils;
 mod traits;
 mod types;
 
 pub use constants::*;
+pub use decomposition::*;
+pub use discards::*;
+pub use hand::*;
+pub use hand_file::*;
+pub use incremental::*;
+pub use necessary_tiles::*;
+pub use tenhou::*;
 pub use test_utils::*;
 pub use traits::*;
 pub use types::*;
+
+#[cfg(feature = "criterion")]
+#[doc(hidden)]
+pub use criterion;
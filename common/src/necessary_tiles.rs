@@ -0,0 +1,114 @@
+use crate::constants::{MAX_NUM_TILE, NUM_TILE_TYPE};
+use crate::traits::ShantenCalculator;
+use crate::types::{TileCounts, TileFlags};
+
+/// Result of a necessary tile calculation for a 3n+1 hand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NecessaryTiles {
+    /// The shanten number of the hand.
+    pub shanten: i8,
+    /// Tile types that reduce the shanten number when drawn.
+    pub tiles: TileFlags,
+    /// Number of remaining copies of the tiles in `tiles`,
+    /// excluding the copies already in the hand.
+    pub num_tiles: u8,
+}
+
+impl NecessaryTiles {
+    /// Creates a new instance and counts the remaining copies of `tiles`.
+    ///
+    /// Tile types of which the hand already holds all copies are removed from `tiles`,
+    /// since they can never be drawn.
+    ///
+    /// # Arguments
+    ///
+    /// * `shanten` - The shanten number of the hand
+    /// * `tiles` - Tile types that reduce the shanten number
+    /// * `hand` - Reference to a [TileCounts] struct representing the hand's tile counts
+    pub fn new(shanten: i8, mut tiles: TileFlags, hand: &TileCounts) -> Self {
+        let mut num_tiles = 0;
+        for (i, &c) in hand.iter().enumerate() {
+            if c as usize >= MAX_NUM_TILE {
+                tiles &= !(1 << i);
+            } else if tiles & (1 << i) != 0 {
+                num_tiles += MAX_NUM_TILE as u8 - c;
+            }
+        }
+
+        NecessaryTiles {
+            shanten,
+            tiles,
+            num_tiles,
+        }
+    }
+
+    /// Returns `true` if the tile type reduces the shanten number.
+    ///
+    /// # Arguments
+    ///
+    /// * `tile` - Index of the tile type (0–33)
+    pub fn contains(&self, tile: usize) -> bool {
+        self.tiles & (1 << tile) != 0
+    }
+}
+
+/// Calculates the necessary tiles of a 3n+1 hand by trying every possible draw.
+///
+/// This is the default implementation of [`ShantenCalculator::calculate_necessary_tiles`].
+/// It calls [`ShantenCalculator::calculate_shanten`] once for the hand itself and once for
+/// each tile type that can still be drawn.
+///
+/// # Arguments
+///
+/// * `calculator` - The calculator used to calculate the shanten numbers
+/// * `hand` - Reference to a [TileCounts] struct representing the hand's tile counts
+///
+/// # Returns
+///
+/// The [NecessaryTiles] of the hand.
+pub fn calculate_necessary_tiles_by_draw<C: ShantenCalculator + ?Sized>(
+    calculator: &C,
+    hand: &TileCounts,
+) -> NecessaryTiles {
+    let shanten = calculator.calculate_shanten(hand);
+    let mut hand_clone = *hand;
+    let mut tiles: TileFlags = 0;
+
+    for i in 0..NUM_TILE_TYPE {
+        if hand_clone[i] as usize >= MAX_NUM_TILE {
+            continue;
+        }
+
+        hand_clone[i] += 1;
+        if calculator.calculate_shanten(&hand_clone) < shanten {
+            tiles |= 1 << i;
+        }
+        hand_clone[i] -= 1;
+    }
+
+    NecessaryTiles::new(shanten, tiles, hand)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TileCountsExt;
+
+    #[test]
+    fn test_new_counts_remaining_copies() {
+        let hand = TileCounts::from_code("123m456p789s1112z");
+        let tiles = (1 << 27) | (1 << 28) | (1 << 29);
+        let necessary = NecessaryTiles::new(0, tiles, &hand);
+        assert_eq!(necessary.tiles, tiles);
+        assert_eq!(necessary.num_tiles, 1 + 3 + 4);
+    }
+
+    #[test]
+    fn test_new_removes_tiles_held_in_all_copies() {
+        let hand = TileCounts::from_code("1111m");
+        let necessary = NecessaryTiles::new(1, (1 << 0) | (1 << 1), &hand);
+        assert!(!necessary.contains(0));
+        assert!(necessary.contains(1));
+        assert_eq!(necessary.num_tiles, 4);
+    }
+}
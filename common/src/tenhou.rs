@@ -0,0 +1,281 @@
+use std::fmt;
+
+use crate::constants::{MAX_NUM_TILE, NUM_TILE_TYPE};
+use crate::types::TileCounts;
+
+/// Number of suits (m, p, s).
+const NUM_SUIT: usize = 3;
+
+/// Result of parsing a Tenhou-style hand string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct ParsedHand {
+    /// Tile counts of the hand. Red fives are counted as normal fives.
+    pub tiles: TileCounts,
+    /// Number of red fives in each suit (m, p, s).
+    pub red_fives: [u8; NUM_SUIT],
+}
+
+/// Error returned when a Tenhou-style hand string cannot be parsed.
+///
+/// Each variant holds the index of the offending character, counted in characters.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParseError {
+    /// The character is neither a digit nor a type (`m`, `p`, `s`, `z`).
+    InvalidCharacter {
+        /// Index of the character
+        index: usize,
+        /// The character
+        character: char,
+    },
+    /// The tile number does not exist for its type (e.g. `8z` or `0z`).
+    InvalidTileNumber {
+        /// Index of the tile number
+        index: usize,
+        /// The tile number
+        number: u8,
+        /// The type of the tile
+        tile_type: char,
+    },
+    /// No type is specified after the tile number (e.g. `123`).
+    MissingType {
+        /// Index of the first tile number without a type
+        index: usize,
+    },
+    /// The hand holds more than 4 copies of the tile type.
+    TooManyCopies {
+        /// Index of the tile number that exceeds the limit
+        index: usize,
+        /// Index of the tile type (0–33)
+        tile: usize,
+    },
+}
+
+impl ParseError {
+    /// Returns the index of the offending character, counted in characters.
+    pub fn index(&self) -> usize {
+        match *self {
+            ParseError::InvalidCharacter { index, .. }
+            | ParseError::InvalidTileNumber { index, .. }
+            | ParseError::MissingType { index }
+            | ParseError::TooManyCopies { index, .. } => index,
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            ParseError::InvalidCharacter { index, character } => {
+                write!(f, "invalid character {character:?} at index {index}")
+            }
+            ParseError::InvalidTileNumber {
+                index,
+                number,
+                tile_type,
+            } => write!(
+                f,
+                "tile {number}{tile_type} does not exist at index {index}"
+            ),
+            ParseError::MissingType { index } => {
+                write!(
+                    f,
+                    "no type specified after the tile number at index {index}"
+                )
+            }
+            ParseError::TooManyCopies { index, tile } => write!(
+                f,
+                "more than {MAX_NUM_TILE} copies of tile {tile} at index {index}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Parses a Tenhou-style hand string (e.g. `"123m406p789s11z"`).
+///
+/// Digits are followed by their type: `m` (characters), `p` (circles), `s` (bamboos)
+/// or `z` (honors, `1`–`7`). `0` in a suit denotes a red five.
+///
+/// # Arguments
+///
+/// * `code` - A string representing the hand
+///
+/// # Returns
+///
+/// The [ParsedHand], or a [ParseError] pointing at the offending character.
+pub fn parse_hand(code: &str) -> Result<ParsedHand, ParseError> {
+    let mut tiles = [0; NUM_TILE_TYPE];
+    let mut red_fives = [0; NUM_SUIT];
+    let mut numbers: Vec<(usize, u8)> = Vec::new();
+
+    for (index, character) in code.chars().enumerate() {
+        if let Some(d) = character.to_digit(10) {
+            numbers.push((index, d as u8));
+            continue;
+        }
+
+        let base = match character {
+            'm' => 0,
+            'p' => 9,
+            's' => 18,
+            'z' => 27,
+            _ => return Err(ParseError::InvalidCharacter { index, character }),
+        };
+
+        for (index, number) in numbers.drain(..) {
+            let tile = match (character, number) {
+                ('z', 1..=7) | ('m' | 'p' | 's', 1..=9) => base + number as usize - 1,
+                // red five
+                ('m' | 'p' | 's', 0) => {
+                    red_fives[base / 9] += 1;
+                    base + 4
+                }
+                _ => {
+                    return Err(ParseError::InvalidTileNumber {
+                        index,
+                        number,
+                        tile_type: character,
+                    });
+                }
+            };
+
+            if tiles[tile] as usize >= MAX_NUM_TILE {
+                return Err(ParseError::TooManyCopies { index, tile });
+            }
+            tiles[tile] += 1;
+        }
+    }
+
+    if let Some(&(index, _)) = numbers.first() {
+        return Err(ParseError::MissingType { index });
+    }
+
+    Ok(ParsedHand { tiles, red_fives })
+}
+
+/// Formats tile counts as a canonical Tenhou-style hand string (e.g. `"123m456p789s11z"`).
+///
+/// Types are written in the order `m`, `p`, `s`, `z`, each with its tile numbers in
+/// ascending order. Types without tiles are omitted.
+/// The result can be parsed back with [parse_hand].
+///
+/// # Arguments
+///
+/// * `hand` - Reference to a [TileCounts] struct representing the hand's tile counts
+///
+/// # Returns
+///
+/// The hand string. An empty hand yields an empty string.
+pub fn to_code(hand: &TileCounts) -> String {
+    const TYPES: [(char, usize, usize); 4] = [('m', 0, 9), ('p', 9, 9), ('s', 18, 9), ('z', 27, 7)];
+    let mut code = String::new();
+
+    for (tile_type, base, len) in TYPES {
+        let mut has_tile = false;
+        for (n, &c) in hand[base..base + len].iter().enumerate() {
+            for _ in 0..c {
+                code.push(char::from(b'1' + n as u8));
+                has_tile = true;
+            }
+        }
+        if has_tile {
+            code.push(tile_type);
+        }
+    }
+
+    code
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::TileCountsExt;
+
+    #[test]
+    fn test_parse_hand() {
+        let parsed = parse_hand("123m456p789s12344z").unwrap();
+        assert_eq!(parsed.tiles, TileCounts::from_code("123m456p789s12344z"));
+        assert_eq!(parsed.red_fives, [0, 0, 0]);
+    }
+
+    #[test]
+    fn test_parse_hand_red_fives() {
+        let parsed = parse_hand("405m0p55s").unwrap();
+        assert_eq!(parsed.tiles, TileCounts::from_code("455m5p55s"));
+        assert_eq!(parsed.red_fives, [1, 1, 0]);
+    }
+
+    #[test]
+    fn test_parse_hand_invalid_character() {
+        let err = parse_hand("123m 456p").unwrap_err();
+        assert_eq!(
+            err,
+            ParseError::InvalidCharacter {
+                index: 4,
+                character: ' '
+            }
+        );
+        assert_eq!(err.index(), 4);
+    }
+
+    #[test]
+    fn test_parse_hand_invalid_tile_number() {
+        assert_eq!(
+            parse_hand("118z").unwrap_err(),
+            ParseError::InvalidTileNumber {
+                index: 2,
+                number: 8,
+                tile_type: 'z'
+            }
+        );
+        assert_eq!(parse_hand("1m0z").unwrap_err().index(), 2);
+    }
+
+    #[test]
+    fn test_parse_hand_missing_type() {
+        assert_eq!(
+            parse_hand("123m456").unwrap_err(),
+            ParseError::MissingType { index: 4 }
+        );
+    }
+
+    #[test]
+    fn test_parse_hand_too_many_copies() {
+        assert_eq!(
+            parse_hand("55505m").unwrap_err(),
+            ParseError::TooManyCopies { index: 4, tile: 4 }
+        );
+        assert_eq!(
+            parse_hand("11111z").unwrap_err(),
+            ParseError::TooManyCopies { index: 4, tile: 27 }
+        );
+    }
+
+    #[test]
+    fn test_to_code() {
+        let hand = TileCounts::from_code("11z789s456p123m");
+        assert_eq!(to_code(&hand), "123m456p789s11z");
+        assert_eq!(
+            to_code(&TileCounts::from_code("1112345678999p")),
+            "1112345678999p"
+        );
+        assert_eq!(to_code(&[0; NUM_TILE_TYPE]), "");
+    }
+
+    #[test]
+    fn test_to_code_round_trip() {
+        for code in [
+            "123m456p789s11222z",
+            "19m19p19s1234567z",
+            "44445555z",
+            "5p",
+            "",
+        ] {
+            assert_eq!(to_code(&parse_hand(code).unwrap().tiles), code);
+        }
+
+        let hand = TileCounts::from_code("0m55p5s1234567z");
+        assert_eq!(parse_hand(&to_code(&hand)).unwrap().tiles, hand);
+    }
+}
@@ -17,7 +17,9 @@ macro_rules! shanten_test_case {
         let counts = TileCounts::from_code(&$hand);
         assert_eq!(
             $crate::ShantenCalculator::calculate_shanten(&calculator, &counts),
-            $expected
+            $expected,
+            "hand: {}",
+            $crate::to_code(&counts)
         );
     };
 }
@@ -237,6 +239,776 @@ macro_rules! shanten_tests {
                 // Source: https://zenn.dev/tomohxx/articles/aecace4e3a3bc1
                 shanten_test_case!($calculator_type, "23m111122223333z", 2);
             }
+
+            #[test]
+            fn test_try_shanten() {
+                let calculator = <$calculator_type as $crate::ShantenCalculator>::new();
+                let counts = TileCounts::from_code("123m456p789s11222z");
+                assert_eq!(
+                    $crate::ShantenCalculator::try_calculate_shanten(&calculator, &counts),
+                    Ok($crate::ShantenCalculator::calculate_shanten(
+                        &calculator,
+                        &counts
+                    ))
+                );
+
+                let mut counts = TileCounts::from_code("11112222333344z");
+                counts[27] = 5;
+                counts[30] = 0;
+                assert_eq!(
+                    $crate::ShantenCalculator::try_calculate_shanten(&calculator, &counts),
+                    Err($crate::HandError::TooManyCopies(27))
+                );
+
+                let counts = TileCounts::from_code("123456789m123456p");
+                assert_eq!(
+                    $crate::ShantenCalculator::try_calculate_shanten(&calculator, &counts),
+                    Err($crate::HandError::InvalidHandSize(15))
+                );
+            }
+
+            #[test]
+            fn test_batch_shanten() {
+                let calculator = <$calculator_type as $crate::ShantenCalculator>::new();
+                let hands = [
+                    TileCounts::from_code("123m456p789s11222z"),
+                    TileCounts::from_code("123m456p789s1122z"),
+                    TileCounts::from_code("1m"),
+                ];
+                let mut shantens = [i8::MAX; 3];
+                $crate::ShantenCalculator::calculate_shanten_batch(
+                    &calculator,
+                    &hands,
+                    &mut shantens,
+                );
+                let expected =
+                    hands.map(|h| $crate::ShantenCalculator::calculate_shanten(&calculator, &h));
+                assert_eq!(shantens, expected);
+            }
+        }
+    };
+}
+
+/// Macro for a single necessary tile calculation test case.
+///
+/// Checks that [`ShantenCalculator::calculate_necessary_tiles`](crate::ShantenCalculator::calculate_necessary_tiles)
+/// returns the same result as [`calculate_necessary_tiles_by_draw`](crate::calculate_necessary_tiles_by_draw).
+///
+/// # Arguments
+///
+/// * `$calculator_type` - A type that implements the `ShantenCalculator` trait
+/// * `$hand` - The 3n+1 hand to test (as a `TileCounts`)
+///
+/// # Note
+///
+/// This macro is exported for use in other macros, but users should not call it directly.
+/// Please use [`necessary_tiles_tests!`] to generate test cases.
+#[macro_export]
+macro_rules! necessary_tiles_test_case {
+    ($calculator_type:ty, $hand:expr) => {
+        let calculator = <$calculator_type as $crate::ShantenCalculator>::new();
+        let counts = TileCounts::from_code(&$hand);
+        assert_eq!(
+            $crate::ShantenCalculator::calculate_necessary_tiles(&calculator, &counts),
+            $crate::calculate_necessary_tiles_by_draw(&calculator, &counts)
+        );
+    };
+}
+
+/// Macro to generate necessary tile calculation tests for a specified `ShantenCalculator` type.
+///
+/// Use this macro if your `ShantenCalculator` implementation overrides
+/// `calculate_necessary_tiles`.
+/// Each test checks that the overridden method agrees with the default implementation,
+/// which tries every possible draw.
+///
+/// # Arguments
+///
+/// * `$calculator_type` - A type that implements the `ShantenCalculator` trait
+///
+/// # Example
+///
+/// ```
+/// use common::{necessary_tiles_tests, shanten_tests};
+///
+/// shanten_tests!(YourAlgorithm);
+/// necessary_tiles_tests!(YourAlgorithm);
+/// ```
+#[macro_export]
+macro_rules! necessary_tiles_tests {
+    ($calculator_type:ty) => {
+        #[cfg(test)]
+        mod necessary_tiles_tests {
+            use super::*;
+            use $crate::TileCountsExt;
+            use $crate::necessary_tiles_test_case;
+
+            #[test]
+            fn test_necessary_tiles_thirteen_orphans() {
+                necessary_tiles_test_case!($calculator_type, "19m19p19s1234567z");
+            }
+
+            #[test]
+            fn test_necessary_tiles_tenpai() {
+                necessary_tiles_test_case!($calculator_type, "123m456p789s1122z");
+            }
+
+            #[test]
+            fn test_necessary_tiles_with_meld() {
+                necessary_tiles_test_case!($calculator_type, "123m456p789s2z");
+            }
+
+            #[test]
+            fn test_necessary_tiles_nine_gates() {
+                necessary_tiles_test_case!($calculator_type, "1112345678999m");
+            }
+
+            #[test]
+            fn test_necessary_tiles_without_pair() {
+                necessary_tiles_test_case!($calculator_type, "12389m456p12789s");
+            }
+
+            #[test]
+            fn test_necessary_tiles_too_many_meld_candidates() {
+                necessary_tiles_test_case!($calculator_type, "12389m456p1289s1z");
+            }
+
+            #[test]
+            fn test_necessary_tiles_not_enough_meld_candidates() {
+                necessary_tiles_test_case!($calculator_type, "133345568m2367z");
+            }
+
+            #[test]
+            fn test_necessary_tiles_isolated_tiles() {
+                necessary_tiles_test_case!($calculator_type, "159m26p37s123456z");
+            }
+
+            #[test]
+            fn test_necessary_tiles_sequence_isolated_sequence() {
+                necessary_tiles_test_case!($calculator_type, "2344456p12345z");
+            }
+
+            #[test]
+            fn test_necessary_tiles_full_flush() {
+                necessary_tiles_test_case!($calculator_type, "2234556788p");
+            }
+
+            #[test]
+            fn test_necessary_tiles_waiting_for_the_5th_tile() {
+                necessary_tiles_test_case!($calculator_type, "1111m123p11223s");
+            }
+
+            #[test]
+            fn test_necessary_tiles_4_honors() {
+                necessary_tiles_test_case!($calculator_type, "123m1111z");
+            }
+
+            #[test]
+            fn test_necessary_tiles_lack_isolated_tile_13_4333() {
+                necessary_tiles_test_case!($calculator_type, "1111222333444z");
+            }
+
+            #[test]
+            fn test_necessary_tiles_lack_isolated_tile_13_4441() {
+                necessary_tiles_test_case!($calculator_type, "1111222233334z");
+            }
+
+            #[test]
+            fn test_necessary_tiles_single_tile() {
+                necessary_tiles_test_case!($calculator_type, "5p");
+            }
+        }
+    };
+}
+
+/// Macro to generate discard candidate calculation tests for a specified `ShantenCalculator` type.
+///
+/// # Arguments
+///
+/// * `$calculator_type` - A type that implements the `ShantenCalculator` trait
+///
+/// # Example
+///
+/// ```
+/// use common::{discards_tests, shanten_tests};
+///
+/// shanten_tests!(YourAlgorithm);
+/// discards_tests!(YourAlgorithm);
+/// ```
+#[macro_export]
+macro_rules! discards_tests {
+    ($calculator_type:ty) => {
+        #[cfg(test)]
+        mod discards_tests {
+            use super::*;
+            use $crate::TileCountsExt;
+
+            fn calculate_discards(hand: &str) -> Vec<(usize, i8, u8)> {
+                let calculator = <$calculator_type as $crate::ShantenCalculator>::new();
+                let counts = TileCounts::from_code(hand);
+                $crate::ShantenCalculator::calculate_discards(&calculator, &counts)
+                    .iter()
+                    .map(|d| {
+                        (
+                            d.tile,
+                            d.necessary_tiles.shanten,
+                            d.necessary_tiles.num_tiles,
+                        )
+                    })
+                    .collect()
+            }
+
+            #[test]
+            fn test_discards_tenpai() {
+                let discards = calculate_discards("123459m456p789s11z");
+                assert_eq!(discards.len(), 13);
+                assert_eq!(
+                    discards[..5],
+                    [(8, 0, 7), (0, 1, 32), (4, 1, 32), (1, 1, 26), (3, 1, 26)]
+                );
+                assert!(discards[1..].iter().all(|&(_, shanten, _)| shanten == 1));
+            }
+
+            #[test]
+            fn test_discards_win() {
+                let discards = calculate_discards("123m456p789s11222z");
+                assert_eq!(discards.len(), 11);
+                assert_eq!(
+                    discards[..4],
+                    [(0, 0, 7), (12, 0, 7), (14, 0, 7), (26, 0, 7)]
+                );
+                assert!(discards.iter().all(|&(_, shanten, _)| shanten == 0));
+            }
+        }
+    };
+}
+
+/// Macro to generate decomposition tests for a specified `DecompositionCalculator` type.
+///
+/// # Arguments
+///
+/// * `$calculator_type` - A type that implements the `DecompositionCalculator` trait
+///
+/// # Example
+///
+/// ```
+/// use common::{decompositions_tests, shanten_tests};
+///
+/// shanten_tests!(YourAlgorithm);
+/// decompositions_tests!(YourAlgorithm);
+/// ```
+#[macro_export]
+macro_rules! decompositions_tests {
+    ($calculator_type:ty) => {
+        #[cfg(test)]
+        mod decompositions_tests {
+            use super::*;
+            use $crate::{Block, Decomposition, TileCountsExt};
+
+            #[test]
+            fn test_decompositions_tenpai() {
+                let calculator = <$calculator_type as $crate::ShantenCalculator>::new();
+                let counts = TileCounts::from_code("123m456p789s1122z");
+                let (shanten, decompositions) =
+                    $crate::DecompositionCalculator::calculate_decompositions(&calculator, &counts);
+                let melds = vec![Block::Sequence(0), Block::Sequence(12), Block::Sequence(24)];
+                assert_eq!(shanten, 0);
+                assert_eq!(
+                    decompositions,
+                    [
+                        Decomposition {
+                            pair: Some(27),
+                            melds: melds.clone(),
+                            meld_cands: vec![Block::Pair(28)],
+                            isolated: vec![],
+                        },
+                        Decomposition {
+                            pair: Some(28),
+                            melds,
+                            meld_cands: vec![Block::Pair(27)],
+                            isolated: vec![],
+                        },
+                    ]
+                );
+            }
+
+            #[test]
+            fn test_decompositions_consistent_with_shanten() {
+                let calculator = <$calculator_type as $crate::ShantenCalculator>::new();
+                for hand in [
+                    "123m456p789s11222z",
+                    "123m456p789s2z",
+                    "12389m456p12789s1z",
+                    "12389m456p1289s11z",
+                    "133345568m23677z",
+                    "2234556788p123z",
+                    "159m26p37s1234567z",
+                ] {
+                    let counts = TileCounts::from_code(hand);
+                    let (shanten, decompositions) =
+                        $crate::DecompositionCalculator::calculate_decompositions(
+                            &calculator,
+                            &counts,
+                        );
+                    assert_eq!(
+                        shanten,
+                        $crate::ShantenCalculator::calculate_shanten(&calculator, &counts),
+                        "{hand}"
+                    );
+                    assert!(!decompositions.is_empty(), "{hand}");
+                    for decomposition in &decompositions {
+                        assert_eq!(decomposition.to_tile_counts(), counts, "{hand}");
+                    }
+                    assert!(decompositions.windows(2).all(|w| w[0] < w[1]), "{hand}");
+                }
+            }
+        }
+    };
+}
+
+/// Macro to generate Seven Pairs (Chiitoitsu) shanten number calculation tests
+/// for a specified `ShantenCalculator` type.
+///
+/// Use this macro instead of [`shanten_tests!`] for calculators of the Seven Pairs hand type.
+///
+/// # Arguments
+///
+/// * `$calculator_type` - A type that implements the `ShantenCalculator` trait
+///
+/// # Example
+///
+/// ```
+/// use common::seven_pairs_tests;
+///
+/// seven_pairs_tests!(YourSevenPairsAlgorithm);
+/// ```
+#[macro_export]
+macro_rules! seven_pairs_tests {
+    ($calculator_type:ty) => {
+        #[cfg(test)]
+        mod seven_pairs_tests {
+            use super::*;
+            use $crate::TileCountsExt;
+            use $crate::shanten_test_case;
+
+            #[test]
+            fn test_seven_pairs_shanten_win() {
+                shanten_test_case!($calculator_type, "1133m5577p99s1122z", -1);
+            }
+
+            #[test]
+            fn test_seven_pairs_shanten_tenpai() {
+                shanten_test_case!($calculator_type, "1133m5577p99s112z", 0);
+            }
+
+            #[test]
+            fn test_seven_pairs_shanten_tenpai_14_tiles() {
+                shanten_test_case!($calculator_type, "1133m5577p99s1123z", 0);
+            }
+
+            #[test]
+            fn test_seven_pairs_shanten_no_pairs() {
+                shanten_test_case!($calculator_type, "19m19p19s1234567z", 6);
+            }
+
+            #[test]
+            fn test_seven_pairs_shanten_general_form_win() {
+                shanten_test_case!($calculator_type, "123m456p789s11222z", 4);
+            }
+
+            #[test]
+            fn test_seven_pairs_shanten_triplets() {
+                shanten_test_case!($calculator_type, "111m222p333s4445z", 4);
+            }
+
+            #[test]
+            fn test_seven_pairs_shanten_quad_1() {
+                // A quad cannot be used as two pairs
+                shanten_test_case!($calculator_type, "1111m5577p99s1122z", 1);
+            }
+
+            #[test]
+            fn test_seven_pairs_shanten_quad_2() {
+                shanten_test_case!($calculator_type, "1111222233334z", 6);
+            }
+
+            #[test]
+            fn test_seven_pairs_shanten_quad_3() {
+                shanten_test_case!($calculator_type, "11112222333344z", 5);
+            }
+
+            #[test]
+            fn test_seven_pairs_shanten_short_hand_1() {
+                shanten_test_case!($calculator_type, "1122z", 9);
+            }
+
+            #[test]
+            fn test_seven_pairs_shanten_short_hand_2() {
+                shanten_test_case!($calculator_type, "1z", 12);
+            }
+
+            #[test]
+            fn test_seven_pairs_shanten_short_hand_3() {
+                shanten_test_case!($calculator_type, "1133m5577p99s1z", 2);
+            }
+        }
+    };
+}
+
+/// Macro to generate Thirteen Orphans (Kokushi Musou) shanten number calculation tests
+/// for a specified `ShantenCalculator` type.
+///
+/// Use this macro instead of [`shanten_tests!`] for calculators of the Thirteen Orphans hand type.
+///
+/// # Arguments
+///
+/// * `$calculator_type` - A type that implements the `ShantenCalculator` trait
+///
+/// # Example
+///
+/// ```
+/// use common::thirteen_orphans_tests;
+///
+/// thirteen_orphans_tests!(YourThirteenOrphansAlgorithm);
+/// ```
+#[macro_export]
+macro_rules! thirteen_orphans_tests {
+    ($calculator_type:ty) => {
+        #[cfg(test)]
+        mod thirteen_orphans_tests {
+            use super::*;
+            use $crate::TileCountsExt;
+            use $crate::shanten_test_case;
+
+            #[test]
+            fn test_thirteen_orphans_shanten_win() {
+                shanten_test_case!($calculator_type, "119m19p19s1234567z", -1);
+            }
+
+            #[test]
+            fn test_thirteen_orphans_shanten_thirteen_wait() {
+                shanten_test_case!($calculator_type, "19m19p19s1234567z", 0);
+            }
+
+            #[test]
+            fn test_thirteen_orphans_shanten_tenpai() {
+                shanten_test_case!($calculator_type, "11m19p19s1234567z", 0);
+            }
+
+            #[test]
+            fn test_thirteen_orphans_shanten_1_shanten() {
+                shanten_test_case!($calculator_type, "1119m19p19s12345z", 1);
+            }
+
+            #[test]
+            fn test_thirteen_orphans_shanten_simple_pair() {
+                // A pair of simple tiles does not count
+                shanten_test_case!($calculator_type, "1559m19p19s123456z", 1);
+            }
+
+            #[test]
+            fn test_thirteen_orphans_shanten_general_form_win() {
+                shanten_test_case!($calculator_type, "123m456p789s11222z", 8);
+            }
+
+            #[test]
+            fn test_thirteen_orphans_shanten_all_simples() {
+                shanten_test_case!($calculator_type, "22345678m234567p", 13);
+            }
+
+            #[test]
+            fn test_thirteen_orphans_shanten_short_hand() {
+                shanten_test_case!($calculator_type, "1111m9999p", 10);
+            }
+        }
+    };
+}
+
+/// Macro to generate a test that checks a specified `ShantenCalculator` type
+/// against every single-suit hand.
+///
+/// The test reads `resources/hands_single_suit_all.txt`, generated by `handgen`,
+/// which lists every hand of 3n+1 or 3n+2 tiles in one suit with its shanten number
+/// calculated by definition.
+/// The file is read by [`load_single_suit_hands`](crate::load_single_suit_hands).
+///
+/// # Arguments
+///
+/// * `$calculator_type` - A type that implements the `ShantenCalculator` trait
+/// * `ignore` - Optional. Marks the test as ignored, for algorithms that are known
+///   not to be exact for every hand. Run it with `cargo test -- --ignored`.
+///
+/// # Example
+///
+/// ```
+/// use common::{shanten_tests, single_suit_tests};
+///
+/// shanten_tests!(YourAlgorithm);
+/// single_suit_tests!(YourAlgorithm);
+///
+/// shanten_tests!(YourInexactAlgorithm);
+/// single_suit_tests!(YourInexactAlgorithm, ignore);
+/// ```
+#[macro_export]
+macro_rules! single_suit_tests {
+    (@impl $calculator_type:ty, $(#[$attr:meta])*) => {
+        #[cfg(test)]
+        mod single_suit_tests {
+            use super::*;
+
+            #[test]
+            $(#[$attr])*
+            fn test_shanten_single_suit_all() {
+                const MAX_REPORTS: usize = 10;
+                let calculator = <$calculator_type as $crate::ShantenCalculator>::new();
+                let path = $crate::resource_path("hands_single_suit_all.txt");
+                let cases = $crate::load_single_suit_hands(&path)
+                    .unwrap_or_else(|e| panic!("{}: {e}", path.display()));
+
+                let mut mismatches = Vec::new();
+                for (counts, expected) in cases {
+                    let shanten =
+                        $crate::ShantenCalculator::calculate_shanten(&calculator, &counts);
+                    if shanten != expected {
+                        mismatches.push(format!(
+                            "{}: expected {expected}, got {shanten}",
+                            $crate::to_code(&counts)
+                        ));
+                    }
+                }
+
+                assert!(
+                    mismatches.is_empty(),
+                    "{} mismatches, including:\n{}",
+                    mismatches.len(),
+                    mismatches[..mismatches.len().min(MAX_REPORTS)].join("\n")
+                );
+            }
+        }
+    };
+    ($calculator_type:ty) => {
+        $crate::single_suit_tests!(@impl $calculator_type,);
+    };
+    ($calculator_type:ty, ignore) => {
+        $crate::single_suit_tests!(
+            @impl $calculator_type,
+            #[ignore = "the algorithm is not exact for every single-suit hand"]
+        );
+    };
+}
+
+/// Macro to generate a test that checks a specified `ShantenCalculator` type
+/// against the annotated random hands.
+///
+/// The test reads `resources/hands_<category>_annotated_10000.txt` for the normal, half flush,
+/// full flush and thirteen orphans categories (40,000 hands in total), generated by `handgen`.
+/// Each line lists the tile indices (0–33) of a hand, `|`, and its general form,
+/// Seven Pairs and Thirteen Orphans shanten numbers, e.g. `0 1 2 ... 33 | 3 5 9`.
+/// The files are read by [`load_annotated_hands`](crate::load_annotated_hands).
+///
+/// # Arguments
+///
+/// * `$calculator_type` - A type that implements the `ShantenCalculator` trait
+/// * `$form` - Optional shanten number to compare with: `general` (default), `seven_pairs`,
+///   `thirteen_orphans`, or `overall` for the minimum of the three
+///
+/// # Example
+///
+/// ```
+/// use common::{annotated_tests, seven_pairs_tests, shanten_tests};
+///
+/// shanten_tests!(YourAlgorithm);
+/// annotated_tests!(YourAlgorithm);
+///
+/// seven_pairs_tests!(YourSevenPairsAlgorithm);
+/// annotated_tests!(YourSevenPairsAlgorithm, seven_pairs);
+/// ```
+#[macro_export]
+macro_rules! annotated_tests {
+    (@expected $hand:ident, general) => {
+        $hand.general
+    };
+    (@expected $hand:ident, seven_pairs) => {
+        $hand.seven_pairs
+    };
+    (@expected $hand:ident, thirteen_orphans) => {
+        $hand.thirteen_orphans
+    };
+    (@expected $hand:ident, overall) => {
+        $hand.general.min($hand.seven_pairs).min($hand.thirteen_orphans)
+    };
+    ($calculator_type:ty) => {
+        $crate::annotated_tests!($calculator_type, general);
+    };
+    ($calculator_type:ty, $form:ident) => {
+        #[cfg(test)]
+        mod annotated_tests {
+            use super::*;
+
+            #[test]
+            fn test_shanten_annotated_40000() {
+                const CATEGORIES: [&str; 4] =
+                    ["normal", "half_flush", "full_flush", "thirteen_orphans"];
+                const MAX_REPORTS: usize = 10;
+                let calculator = <$calculator_type as $crate::ShantenCalculator>::new();
+
+                let mut num_hands = 0;
+                let mut mismatches = Vec::new();
+                for category in CATEGORIES {
+                    let path =
+                        $crate::resource_path(&format!("hands_{category}_annotated_10000.txt"));
+                    let hands = $crate::load_annotated_hands(&path)
+                        .unwrap_or_else(|e| panic!("{}: {e}", path.display()));
+                    for hand in hands {
+                        let expected = $crate::annotated_tests!(@expected hand, $form);
+                        let shanten =
+                            $crate::ShantenCalculator::calculate_shanten(&calculator, &hand.tiles);
+                        if shanten != expected {
+                            mismatches.push(format!(
+                                "{}: expected {expected}, got {shanten}",
+                                $crate::to_code(&hand.tiles)
+                            ));
+                        }
+                        num_hands += 1;
+                    }
+                }
+
+                assert_eq!(num_hands, 40_000);
+                assert!(
+                    mismatches.is_empty(),
+                    "{} mismatches, including:\n{}",
+                    mismatches.len(),
+                    mismatches[..mismatches.len().min(MAX_REPORTS)].join("\n")
+                );
+            }
+        }
+    };
+}
+
+/// Macro to generate tests that check the [`IncrementalCalculator`](crate::IncrementalCalculator)
+/// of a specified `GroupCalculator` type against its `calculate_shanten`.
+///
+/// The tests replay deterministic draw/discard sequences and compare
+/// the shanten number after each step.
+///
+/// # Arguments
+///
+/// * `$calculator_type` - A type that implements the `GroupCalculator` trait
+///
+/// # Example
+///
+/// ```
+/// use common::{incremental_tests, shanten_tests};
+///
+/// shanten_tests!(YourAlgorithm);
+/// incremental_tests!(YourAlgorithm);
+/// ```
+#[macro_export]
+macro_rules! incremental_tests {
+    ($calculator_type:ty) => {
+        #[cfg(test)]
+        mod incremental_tests {
+            use super::*;
+            use $crate::{IncrementalCalculator, NUM_TILE_TYPE, TileCounts, TileCountsExt};
+
+            /// Replays draws and discards chosen by a linear congruential generator,
+            /// so that the sequence is the same on every run.
+            fn check_sequence(hand: &str, seed: u64, num_steps: usize) {
+                let calculator = <$calculator_type as $crate::ShantenCalculator>::new();
+                let mut incremental =
+                    IncrementalCalculator::new(&calculator, &TileCounts::from_code(hand));
+                let mut state = seed;
+                let mut next = |n: usize| {
+                    state = state
+                        .wrapping_mul(6364136223846793005)
+                        .wrapping_add(1442695040888963407);
+                    (state >> 33) as usize % n
+                };
+
+                for step in 0..num_steps {
+                    let candidates: Vec<usize> = (0..NUM_TILE_TYPE)
+                        .filter(|&i| {
+                            let c = incremental.hand()[i];
+                            if step % 2 == 0 { c < 4 } else { c > 0 }
+                        })
+                        .collect();
+                    let tile = candidates[next(candidates.len())];
+                    if step % 2 == 0 {
+                        incremental.draw(tile);
+                    } else {
+                        incremental.discard(tile);
+                    }
+
+                    let hand = *incremental.hand();
+                    let expected = $crate::ShantenCalculator::calculate_shanten(&calculator, &hand);
+                    assert_eq!(
+                        incremental.shanten(),
+                        expected,
+                        "step: {}, hand: {}",
+                        step,
+                        $crate::to_code(&hand)
+                    );
+                }
+            }
+
+            #[test]
+            fn test_incremental_initial() {
+                let calculator = <$calculator_type as $crate::ShantenCalculator>::new();
+                let hand = TileCounts::from_code("123m456p789s1122z");
+                let incremental = IncrementalCalculator::new(&calculator, &hand);
+                assert_eq!(incremental.hand(), &hand);
+                assert_eq!(incremental.shanten(), 0);
+            }
+
+            #[test]
+            fn test_incremental_honor_updates() {
+                let calculator = <$calculator_type as $crate::ShantenCalculator>::new();
+                let mut incremental = IncrementalCalculator::new(
+                    &calculator,
+                    &TileCounts::from_code("123m456p789s1234z"),
+                );
+                assert_eq!(incremental.shanten(), 2);
+                // Only the group of the honors changes, while the melds in the suits are kept
+                incremental.draw(27);
+                assert_eq!(incremental.shanten(), 1);
+                incremental.discard(28);
+                assert_eq!(incremental.shanten(), 1);
+                incremental.draw(29);
+                assert_eq!(incremental.shanten(), 0);
+                incremental.discard(27);
+                assert_eq!(incremental.shanten(), 1);
+            }
+
+            #[test]
+            fn test_incremental_sequence_closed_hand() {
+                check_sequence("19m19p19s1234567z", 1, 400);
+                check_sequence("123456789m12345p", 2, 400);
+            }
+
+            #[test]
+            fn test_incremental_sequence_short_hand() {
+                check_sequence("1m", 3, 200);
+                check_sequence("2468m", 4, 200);
+                check_sequence("3579p135s", 5, 200);
+            }
+
+            #[test]
+            #[should_panic]
+            fn test_incremental_discard_missing_tile() {
+                let calculator = <$calculator_type as $crate::ShantenCalculator>::new();
+                let mut incremental =
+                    IncrementalCalculator::new(&calculator, &TileCounts::from_code("123m"));
+                incremental.discard(33);
+            }
+
+            #[test]
+            #[should_panic]
+            fn test_incremental_draw_fifth_copy() {
+                let calculator = <$calculator_type as $crate::ShantenCalculator>::new();
+                let mut incremental =
+                    IncrementalCalculator::new(&calculator, &TileCounts::from_code("1111m"));
+                incremental.draw(0);
+            }
         }
     };
 }
@@ -1,13 +1,17 @@
+use crate::tenhou::parse_hand;
 use crate::types::TileCounts;
 
 /// Extension trait for [TileCounts] to support conversion from Tenhou-style hand strings.
 ///
 /// This trait is intended for internal use in macros and test utilities.
 /// Algorithm implementers do not need to use this trait directly.
+/// To parse hand strings from users, use [`parse_hand`](crate::parse_hand) instead.
 pub trait TileCountsExt {
     /// Converts a Tenhou-style hand string into an array representing
     /// the counts of 34 types of tiles.
     ///
+    /// This is a thin wrapper around [`parse_hand`](crate::parse_hand) that panics on error.
+    ///
     /// # Arguments
     ///
     /// * `hand` - A string representing the hand (e.g., "123m456p789s12344z")
@@ -20,23 +24,10 @@ pub trait TileCountsExt {
 
 impl TileCountsExt for TileCounts {
     fn from_code(hand: &str) -> TileCounts {
-        const TILE_MAP: [(char, usize); 4] = [('m', 0), ('p', 9), ('s', 18), ('z', 27)];
-        let mut current_type: Option<usize> = None;
-        let mut result: TileCounts = [0u8; 34];
-
-        for c in hand.chars().rev() {
-            if let Some(&(_, idx)) = TILE_MAP.iter().find(|&&(t, _)| t == c) {
-                current_type = Some(idx);
-            } else if let Some(d) = c.to_digit(10) {
-                if !(1..=9).contains(&d) {
-                    panic!("tile number must be between 1 and 9, got {d}");
-                }
-                let base = current_type.expect("no type specified before the tile number");
-                let offset = d as usize - 1;
-                result[base + offset] += 1;
-            }
+        match parse_hand(hand) {
+            Ok(parsed) => parsed.tiles,
+            Err(e) => panic!("invalid hand {hand:?}: {e}"),
         }
-        result
     }
 }
 
@@ -76,16 +67,22 @@ mod tests {
     }
 
     #[test]
-    #[should_panic(expected = "no type specified before the tile number")]
+    #[should_panic(expected = "no type specified after the tile number")]
     fn test_from_code_no_type() {
         TileCounts::from_code("123456");
     }
 
+    #[test]
+    fn test_from_code_red_five() {
+        // 0m is a red five
+        assert_eq!(TileCounts::from_code("0m"), TileCounts::from_code("5m"));
+    }
+
     #[test]
     #[should_panic]
     fn test_from_code_offset_out_of_range_number() {
-        // 0m does not exist
-        TileCounts::from_code("0m");
+        // 0z does not exist
+        TileCounts::from_code("0z");
     }
 
     #[test]
@@ -1,3 +1,7 @@
+use crate::decomposition::Decomposition;
+use crate::discards::{Discard, calculate_discards};
+use crate::hand::{Hand, HandError, ValidatedTileCounts};
+use crate::necessary_tiles::{NecessaryTiles, calculate_necessary_tiles_by_draw};
 use crate::types::TileCounts;
 
 /// Trait for implementing shanten number calculation algorithms.
@@ -21,4 +25,217 @@ pub trait ShantenCalculator {
     /// The shanten number.
     /// Return `0` for tenpai (ready hand), and `-1` for a winning hand.
     fn calculate_shanten(&self, hand: &TileCounts) -> i8;
+
+    /// Validates a hand and calculates its shanten number.
+    ///
+    /// Unlike [`calculate_shanten`](Self::calculate_shanten), this method rejects hands that
+    /// are not well-formed instead of returning a meaningless number.
+    /// The hand is validated with [ValidatedTileCounts::new];
+    /// see [`validate_tile_counts`](crate::validate_tile_counts) for the conditions.
+    ///
+    /// # Arguments
+    ///
+    /// * `hand` - Reference to a [TileCounts] struct representing the hand's tile counts
+    ///
+    /// # Returns
+    ///
+    /// The shanten number, or a [HandError] if the hand is not well-formed.
+    fn try_calculate_shanten(&self, hand: &TileCounts) -> Result<i8, HandError> {
+        let hand = ValidatedTileCounts::new(*hand)?;
+        Ok(self.calculate_shanten(hand.tile_counts()))
+    }
+
+    /// Calculates the shanten number of a hand with declared melds.
+    ///
+    /// Each declared meld, including a kan, counts as one completed meld.
+    /// The general form depends only on the number of declared melds, not on their tiles,
+    /// since no tile of a declared meld can be used elsewhere.
+    /// The default implementation passes the concealed tiles to
+    /// [`calculate_shanten`](Self::calculate_shanten), which derives the number of melds
+    /// from the number of tiles. [Hand] guarantees that this equals the number of declared melds.
+    ///
+    /// # Arguments
+    ///
+    /// * `hand` - Reference to a [Hand] struct representing the concealed tiles and the melds
+    ///
+    /// # Returns
+    ///
+    /// The shanten number.
+    /// Return `0` for tenpai (ready hand), and `-1` for a winning hand.
+    fn calculate_shanten_with_melds(&self, hand: &Hand) -> i8 {
+        let num_tiles = hand.concealed().iter().sum::<u8>() as usize;
+        debug_assert_eq!(4 - num_tiles / 3, hand.melds().len());
+        self.calculate_shanten(hand.concealed())
+    }
+
+    /// Calculates the shanten numbers of many hands at once.
+    ///
+    /// The default implementation calls [`calculate_shanten`](Self::calculate_shanten)
+    /// for each hand in order.
+    /// If your algorithm can share work between hands, please override this method.
+    ///
+    /// # Arguments
+    ///
+    /// * `hands` - Slice of [TileCounts] structs representing the hands' tile counts
+    /// * `shantens` - Slice to write the shanten number of each hand into
+    ///
+    /// # Panics
+    ///
+    /// Panics if `hands` and `shantens` have different lengths.
+    fn calculate_shanten_batch(&self, hands: &[TileCounts], shantens: &mut [i8]) {
+        assert_eq!(
+            hands.len(),
+            shantens.len(),
+            "hands and shantens must have the same length"
+        );
+        for (hand, shanten) in hands.iter().zip(shantens.iter_mut()) {
+            *shanten = self.calculate_shanten(hand);
+        }
+    }
+
+    /// Calculates the shanten numbers of many hands at once in parallel using rayon.
+    ///
+    /// This method is available with the `rayon` feature.
+    /// The default implementation splits the hands into chunks and passes each chunk to
+    /// [`calculate_shanten_batch`](Self::calculate_shanten_batch) on the rayon thread pool,
+    /// so an overridden batch method is also used here.
+    ///
+    /// # Arguments
+    ///
+    /// * `hands` - Slice of [TileCounts] structs representing the hands' tile counts
+    /// * `shantens` - Slice to write the shanten number of each hand into
+    ///
+    /// # Panics
+    ///
+    /// Panics if `hands` and `shantens` have different lengths.
+    #[cfg(feature = "rayon")]
+    fn calculate_shanten_batch_parallel(&self, hands: &[TileCounts], shantens: &mut [i8])
+    where
+        Self: Sync,
+    {
+        use rayon::prelude::*;
+
+        const CHUNK_SIZE: usize = 256;
+        assert_eq!(
+            hands.len(),
+            shantens.len(),
+            "hands and shantens must have the same length"
+        );
+        hands
+            .par_chunks(CHUNK_SIZE)
+            .zip(shantens.par_chunks_mut(CHUNK_SIZE))
+            .for_each(|(hands, shantens)| self.calculate_shanten_batch(hands, shantens));
+    }
+
+    /// Calculates the necessary tiles of a 3n+1 hand.
+    ///
+    /// The necessary tiles are the tile types that reduce the shanten number when drawn.
+    /// The default implementation calls [`calculate_shanten`](Self::calculate_shanten)
+    /// for every possible draw.
+    /// If your algorithm can derive them from its own search, please override this method.
+    ///
+    /// # Arguments
+    ///
+    /// * `hand` - Reference to a [TileCounts] struct representing the hand's tile counts.
+    ///   The number of tiles must be 3n+1.
+    ///
+    /// # Returns
+    ///
+    /// The [NecessaryTiles] of the hand.
+    fn calculate_necessary_tiles(&self, hand: &TileCounts) -> NecessaryTiles {
+        calculate_necessary_tiles_by_draw(self, hand)
+    }
+
+    /// Calculates every discard candidate of a 3n+2 hand.
+    ///
+    /// The default implementation calls
+    /// [`calculate_necessary_tiles`](Self::calculate_necessary_tiles)
+    /// once for each tile type in the hand.
+    ///
+    /// # Arguments
+    ///
+    /// * `hand` - Reference to a [TileCounts] struct representing the hand's tile counts.
+    ///   The number of tiles must be 3n+2.
+    ///
+    /// # Returns
+    ///
+    /// One [Discard] for each tile type in the hand, sorted best-first.
+    /// See [`calculate_discards`](crate::calculate_discards) for the order.
+    fn calculate_discards(&self, hand: &TileCounts) -> Vec<Discard> {
+        calculate_discards(self, hand)
+    }
+}
+
+/// Trait for shanten number calculation algorithms based on block decomposition.
+///
+/// Implement this trait in addition to [ShantenCalculator] if your algorithm can report
+/// the decompositions it finds.
+pub trait DecompositionCalculator: ShantenCalculator {
+    /// Calculates all decompositions of a hand that achieve the minimum shanten number.
+    ///
+    /// # Arguments
+    ///
+    /// * `hand` - Reference to a [TileCounts] struct representing the hand's tile counts
+    ///
+    /// # Returns
+    ///
+    /// The shanten number and the [Decomposition]s that achieve it, sorted and without duplicates.
+    fn calculate_decompositions(&self, hand: &TileCounts) -> (i8, Vec<Decomposition>);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::NUM_TILE_TYPE;
+
+    /// Calculator that returns the number of tiles, to check which hand each result belongs to.
+    struct NumTiles {}
+
+    impl ShantenCalculator for NumTiles {
+        fn new() -> Self {
+            NumTiles {}
+        }
+
+        fn calculate_shanten(&self, hand: &TileCounts) -> i8 {
+            hand.iter().sum::<u8>() as i8
+        }
+    }
+
+    fn hands() -> Vec<TileCounts> {
+        (0..1000)
+            .map(|i| {
+                let mut hand = [0; NUM_TILE_TYPE];
+                hand[i % NUM_TILE_TYPE] = (i % 5) as u8;
+                hand
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_calculate_shanten_batch() {
+        let calculator = NumTiles::new();
+        let hands = hands();
+        let mut shantens = vec![-1; hands.len()];
+        calculator.calculate_shanten_batch(&hands, &mut shantens);
+        assert!((0..hands.len()).all(|i| shantens[i] == (i % 5) as i8));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_calculate_shanten_batch_length_mismatch() {
+        let calculator = NumTiles::new();
+        let hands = hands();
+        let mut shantens = vec![-1; hands.len() - 1];
+        calculator.calculate_shanten_batch(&hands, &mut shantens);
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_calculate_shanten_batch_parallel() {
+        let calculator = NumTiles::new();
+        let hands = hands();
+        let mut shantens = vec![-1; hands.len()];
+        calculator.calculate_shanten_batch_parallel(&hands, &mut shantens);
+        assert!((0..hands.len()).all(|i| shantens[i] == (i % 5) as i8));
+    }
 }
@@ -24,3 +24,9 @@ pub type TileCount = u8;
 /// | ----- | --------- | ---------- | --------- | ---------- | ---------- | ---------- | -------- |
 /// | Tile  | East (1z) | South (2z) | West (3z) | North (4z) | White (5z) | Green (6z) | Red (7z) |
 pub type TileCounts = [TileCount; NUM_TILE_TYPE];
+
+/// Set of tile types represented as a bit field.
+///
+/// Bit `i` is set if the tile type with index `i` is included.
+/// See [TileCounts] for the correspondence between indices and tile types.
+pub type TileFlags = u64;
@@ -0,0 +1,151 @@
+//! Differential tests across the shanten number calculation algorithms.
+//!
+//! Every algorithm is run over the hands in `resources/` and over random hands of
+//! every size, and each hand on which any two algorithms disagree is reported
+//! in Tenhou notation.
+//!
+//! Each test checks in the number of hands on which each algorithm disagrees with
+//! `definition`, so that any new disagreement fails the test, including one of the algorithms
+//! that are not exact for every hand. None of the hands currently hits such a disagreement.
+//! The hands are printed, which can be seen with `cargo test -p difftest -- --nocapture`.
+
+#[cfg(test)]
+mod tests {
+    use common::{MAX_HAND_SIZE, MAX_NUM_TILE, NUM_TILE_TYPE};
+    use common::{ShantenCalculator, TileCounts, to_code};
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use rand::seq::SliceRandom;
+
+    const NUM_RANDOM_CASES: usize = 1_000;
+
+    struct Algorithm {
+        name: &'static str,
+        calculate: Box<dyn Fn(&TileCounts) -> i8>,
+    }
+
+    fn algorithm<C: ShantenCalculator + 'static>(name: &'static str) -> Algorithm {
+        let calculator = C::new();
+        Algorithm {
+            name,
+            calculate: Box::new(move |hand| calculator.calculate_shanten(hand)),
+        }
+    }
+
+    fn algorithms() -> Vec<Algorithm> {
+        vec![
+            algorithm::<definition::Definition>("definition"),
+            algorithm::<decomp_fixed_pruned::DecompFixedPruned>("decomp_fixed_pruned"),
+            algorithm::<dp_min_plus::DpMinPlus>("dp_min_plus"),
+            algorithm::<table::Table>("table"),
+            algorithm::<table_embedded::TableEmbedded>("table_embedded"),
+            algorithm::<decomp::Decomp>("decomp"),
+            algorithm::<decomp_pruned::DecompPruned>("decomp_pruned"),
+            algorithm::<decomp_ara::DecompAra>("decomp_ara"),
+            algorithm::<decomp_ara_removal::DecompAraRemoval>("decomp_ara_removal"),
+            algorithm::<decomp_kobayashi::DecompKobayashi>("decomp_kobayashi"),
+        ]
+    }
+
+    fn load_hands(filename: &str) -> Vec<TileCounts> {
+        let path = common::resource_path(filename);
+        common::load_hands(&path).unwrap_or_else(|e| panic!("{}: {e}", path.display()))
+    }
+
+    fn generate_random_hands(num_tiles: usize) -> Vec<TileCounts> {
+        let mut rng = StdRng::seed_from_u64(num_tiles as u64);
+        let mut wall: [usize; NUM_TILE_TYPE * MAX_NUM_TILE] = std::array::from_fn(|i| i / 4);
+        (0..NUM_RANDOM_CASES)
+            .map(|_| {
+                wall.shuffle(&mut rng);
+                let mut counts = [0; NUM_TILE_TYPE];
+                for &i in &wall[..num_tiles] {
+                    counts[i] += 1;
+                }
+                counts
+            })
+            .collect()
+    }
+
+    /// Checks that the number of hands on which each algorithm disagrees with the reference,
+    /// the first algorithm, is as expected. Algorithms not listed must always agree.
+    ///
+    /// Each hand on which any algorithm disagrees is printed in Tenhou notation.
+    fn check_hands(hands: &[TileCounts], expected_mismatches: &[(&str, usize)]) {
+        let algorithms = algorithms();
+        let mut num_mismatches = vec![0; algorithms.len()];
+
+        for hand in hands {
+            let shantens: Vec<_> = algorithms.iter().map(|a| (a.calculate)(hand)).collect();
+            if shantens.iter().all(|&s| s == shantens[0]) {
+                continue;
+            }
+
+            for (n, &s) in num_mismatches.iter_mut().zip(&shantens) {
+                if s != shantens[0] {
+                    *n += 1;
+                }
+            }
+            let results: Vec<_> = algorithms
+                .iter()
+                .zip(&shantens)
+                .map(|(a, s)| format!("{}={}", a.name, s))
+                .collect();
+            println!("{}: {}", to_code(hand), results.join(", "));
+        }
+
+        let actual: Vec<_> = algorithms
+            .iter()
+            .zip(num_mismatches)
+            .filter(|&(_, n)| n > 0)
+            .map(|(a, n)| (a.name, n))
+            .collect();
+        assert_eq!(
+            actual, expected_mismatches,
+            "unexpected numbers of mismatched hands (see the output for the hands)"
+        );
+    }
+
+    #[test]
+    fn test_normal_10000() {
+        check_hands(&load_hands("hands_normal_10000.txt"), &[]);
+    }
+
+    #[test]
+    fn test_half_flush_10000() {
+        check_hands(&load_hands("hands_half_flush_10000.txt"), &[]);
+    }
+
+    #[test]
+    fn test_full_flush_10000() {
+        check_hands(&load_hands("hands_full_flush_10000.txt"), &[]);
+    }
+
+    #[test]
+    fn test_thirteen_orphans_10000() {
+        check_hands(&load_hands("hands_thirteen_orphans_10000.txt"), &[]);
+    }
+
+    #[test]
+    fn test_game_10000() {
+        check_hands(&load_hands("hands_game_10000.txt"), &[]);
+    }
+
+    #[test]
+    fn test_shanten_buckets_1000() {
+        let hands: Vec<_> = (-1..=6)
+            .flat_map(|shanten| load_hands(&format!("hands_shanten_{shanten}_1000.txt")))
+            .collect();
+        check_hands(&hands, &[]);
+    }
+
+    #[test]
+    fn test_random_hands() {
+        // Hands of 3n tiles are not valid hands
+        let hands: Vec<_> = (1..=MAX_HAND_SIZE)
+            .filter(|n| !n.is_multiple_of(3))
+            .flat_map(generate_random_hands)
+            .collect();
+        check_hands(&hands, &[]);
+    }
+}
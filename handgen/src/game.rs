@@ -0,0 +1,67 @@
+use common::{MAX_HAND_SIZE, MAX_NUM_TILE, NUM_TILE_TYPE, ShantenCalculator, TileCounts};
+use rand::Rng;
+use rand::seq::{IndexedRandom, SliceRandom};
+
+/// Maximum number of draws of a player from the live wall in a four-player game
+pub const MAX_TURN: usize = 18;
+
+const NUM_WALL: usize = NUM_TILE_TYPE * MAX_NUM_TILE;
+
+fn to_tiles(hand: &TileCounts) -> Vec<u8> {
+    hand.iter()
+        .enumerate()
+        .flat_map(|(i, &c)| std::iter::repeat_n(i as u8, c as usize))
+        .collect()
+}
+
+/// Generates a hand in the middle of a game by self-play.
+///
+/// The player is dealt 13 tiles from a shuffled wall, and then draws a tile and discards
+/// the best tile chosen by [`ShantenCalculator::calculate_discards`] on each turn,
+/// i.e. the discard that minimizes the shanten number, then maximizes the number of
+/// necessary tiles. Discards of the other players are not simulated.
+///
+/// # Arguments
+///
+/// * `calculator` - The calculator used to choose the discards
+/// * `rng` - The random number generator used to shuffle the wall and choose the turn
+/// * `turns` - Turn numbers (1–18) at which the hand may be sampled, chosen uniformly
+/// * `size` - 14 to sample the hand right after the draw, or 13 to sample it after the discard
+///
+/// # Returns
+///
+/// The tile indices of the hand in ascending order.
+pub fn generate_game_hand<C: ShantenCalculator>(
+    calculator: &C,
+    rng: &mut impl Rng,
+    turns: &[usize],
+    size: usize,
+) -> Vec<u8> {
+    assert!(
+        size == MAX_HAND_SIZE || size == MAX_HAND_SIZE - 1,
+        "game hands must have 13 or 14 tiles, got {size}"
+    );
+    let turn = *turns.choose(rng).unwrap();
+    assert!((1..=MAX_TURN).contains(&turn), "invalid turn: {turn}");
+
+    let mut wall: [u8; NUM_WALL] = std::array::from_fn(|i| (i / 4) as u8);
+    wall.shuffle(rng);
+    let (dealt, live_wall) = wall.split_at(MAX_HAND_SIZE - 1);
+
+    let mut hand = [0; NUM_TILE_TYPE];
+    for &tile in dealt {
+        hand[tile as usize] += 1;
+    }
+
+    for (i, &tile) in live_wall[..turn].iter().enumerate() {
+        hand[tile as usize] += 1;
+        // Keep the drawn tile on the last turn
+        if i + 1 == turn && size == MAX_HAND_SIZE {
+            break;
+        }
+        let discards = calculator.calculate_discards(&hand);
+        hand[discards[0].tile] -= 1;
+    }
+
+    to_tiles(&hand)
+}
@@ -1,50 +1,253 @@
-use common::{MAX_HAND_SIZE, MAX_NUM_TILE, NUM_TILE_TYPE};
+mod game;
+mod single_suit;
+
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser, ValueEnum};
+use common::{
+    MAX_HAND_SIZE, MAX_NUM_TILE, MIN_HAND_SIZE, NUM_SANMA_TILE_TYPE, NUM_TILE_TYPE,
+    ShantenCalculator, TERMINAL_AND_HONOR_TILES, TileCounts, TileSet, to_code,
+};
+use game::{MAX_TURN, generate_game_hand};
 use rand::rngs::StdRng;
 use rand::seq::{IndexedRandom, SliceRandom};
 use rand::{Rng, SeedableRng};
+use seven_pairs::SevenPairs;
+use single_suit::{SuitCounts, generate_single_suit_hands};
 use std::fs::File;
 use std::io::{BufWriter, Write};
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use table::Table;
+use thirteen_orphans::ThirteenOrphans;
 
 const NUM_WALL: usize = NUM_TILE_TYPE * MAX_NUM_TILE;
+const NUM_SANMA_WALL: usize = NUM_SANMA_TILE_TYPE * MAX_NUM_TILE;
 const NUM_CASES: usize = 10_000;
+const SEED: u64 = 42;
+const TURNS: [usize; 4] = [6, 9, 12, 15];
+const STRATIFIED_SHANTENS: RangeInclusive<i8> = -1..=6;
+const MAX_ATTEMPTS: usize = 100_000_000;
+const SINGLE_SUIT_PATH: &str = "resources/hands_single_suit_all.txt";
 
-fn draw_tiles(wall: &[u8]) -> [u8; MAX_HAND_SIZE] {
-    let mut hand = [0u8; MAX_HAND_SIZE];
-    hand.copy_from_slice(&wall[..MAX_HAND_SIZE]);
-    hand
+/// Category of generated hands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum Category {
+    /// Hands drawn from all 136 tiles
+    Normal,
+    /// Hands drawn from one suit and the honors
+    HalfFlush,
+    /// Hands drawn from one suit
+    FullFlush,
+    /// Hands drawn from the terminal and honor tiles
+    ThirteenOrphans,
+    /// Hands drawn from the 108 tiles of three-player mahjong (without 2m–8m)
+    Sanma,
+    /// Every single-suit hand with its shanten number (ignores the size, count and seed)
+    SingleSuit,
+    /// Hands in the middle of a game, by self-play with greedy discards (13 or 14 tiles)
+    Game,
+}
+
+impl Category {
+    /// Returns the name of the category used in the file name.
+    fn file_stem(&self) -> &'static str {
+        match self {
+            Category::Normal => "normal",
+            Category::HalfFlush => "half_flush",
+            Category::FullFlush => "full_flush",
+            Category::ThirteenOrphans => "thirteen_orphans",
+            Category::Sanma => "sanma",
+            Category::SingleSuit => "single_suit",
+            Category::Game => "game",
+        }
+    }
+}
+
+/// Generates random mahjong hands for benchmarking.
+///
+/// Without --category, generates every file in `resources/`.
+/// The default options reproduce the files in the repository.
+#[derive(Debug, Parser)]
+#[command(version, about)]
+struct Args {
+    /// Category of the hands. If omitted, all categories are generated
+    #[arg(long, value_enum)]
+    category: Option<Category>,
+
+    /// Number of tiles in each hand (3n+1 or 3n+2, up to 14)
+    #[arg(long, default_value_t = MAX_HAND_SIZE, value_parser = parse_hand_size)]
+    size: usize,
+
+    /// Number of hands
+    #[arg(long, default_value_t = NUM_CASES)]
+    count: usize,
+
+    /// Seed of the random number generator
+    #[arg(long, default_value_t = SEED)]
+    seed: u64,
+
+    /// Turn numbers (1–18) at which game hands are sampled, chosen uniformly for each hand
+    #[arg(long, value_delimiter = ',', default_values_t = TURNS, value_parser = parse_turn)]
+    turns: Vec<usize>,
+
+    /// Keep only hands with this shanten number (-1 for a winning hand),
+    /// rejecting the other hands of the category
+    #[arg(long, requires = "category", allow_negative_numbers = true)]
+    shanten: Option<i8>,
+
+    /// Append the general form, Seven Pairs and Thirteen Orphans shanten numbers to each hand
+    #[arg(long, requires = "category")]
+    annotate: bool,
+
+    /// Output file [default: resources/hands_<category>_<count>.txt,
+    /// or resources/hands_<category>_<size>_<count>.txt if the size is not 14,
+    /// with _shanten_<shanten> after the category if --shanten is given
+    /// and _annotated if --annotate is given]
+    #[arg(long, requires = "category")]
+    output: Option<PathBuf>,
+}
+
+fn parse_hand_size(s: &str) -> Result<usize, String> {
+    let size: usize = s.parse().map_err(|e| format!("{e}"))?;
+    if !(MIN_HAND_SIZE..=MAX_HAND_SIZE).contains(&size) || size.is_multiple_of(3) {
+        return Err(format!(
+            "expected 3n+1 or 3n+2 tiles between {MIN_HAND_SIZE} and {MAX_HAND_SIZE}, got {size}"
+        ));
+    }
+    Ok(size)
+}
+
+fn parse_turn(s: &str) -> Result<usize, String> {
+    let turn: usize = s.parse().map_err(|e| format!("{e}"))?;
+    if !(1..=MAX_TURN).contains(&turn) {
+        return Err(format!(
+            "expected a turn between 1 and {MAX_TURN}, got {turn}"
+        ));
+    }
+    Ok(turn)
+}
+
+/// Returns the range of shanten numbers of the general form for hands of `size` tiles.
+fn shanten_range(size: usize) -> RangeInclusive<i8> {
+    let max = 2 * (size / 3) as i8;
+    if size % 3 == 2 { -1..=max } else { 0..=max }
 }
 
-fn generate_normal_hand(rng: &mut impl Rng) -> [u8; MAX_HAND_SIZE] {
+fn default_path(
+    category: Category,
+    size: usize,
+    count: usize,
+    shanten: Option<i8>,
+    annotated: bool,
+) -> PathBuf {
+    let mut stem = category.file_stem().to_string();
+    if let Some(shanten) = shanten {
+        stem += &format!("_shanten_{shanten}");
+    }
+    if annotated {
+        stem += "_annotated";
+    }
+    if category == Category::SingleSuit {
+        PathBuf::from(SINGLE_SUIT_PATH)
+    } else if size == MAX_HAND_SIZE {
+        PathBuf::from(format!("resources/hands_{stem}_{count}.txt"))
+    } else {
+        PathBuf::from(format!("resources/hands_{stem}_{size}_{count}.txt"))
+    }
+}
+
+fn stratified_path(shanten: i8, size: usize, count: usize) -> PathBuf {
+    if size == MAX_HAND_SIZE {
+        PathBuf::from(format!("resources/hands_shanten_{shanten}_{count}.txt"))
+    } else {
+        PathBuf::from(format!(
+            "resources/hands_shanten_{shanten}_{size}_{count}.txt"
+        ))
+    }
+}
+
+fn draw_tiles(wall: &[u8], size: usize) -> Vec<u8> {
+    wall[..size].to_vec()
+}
+
+fn generate_normal_hand(rng: &mut impl Rng, size: usize) -> Vec<u8> {
     let mut wall: [u8; NUM_WALL] = std::array::from_fn(|i| (i / 4) as u8);
     wall.shuffle(rng);
-    draw_tiles(wall.as_slice())
+    draw_tiles(wall.as_slice(), size)
 }
 
-fn generate_half_flush_hand(rng: &mut impl Rng) -> [u8; MAX_HAND_SIZE] {
+fn generate_half_flush_hand(rng: &mut impl Rng, size: usize) -> Vec<u8> {
     let color_start = [0, 9, 18].choose(rng).unwrap();
     let suits: [u8; 9 * 4] = std::array::from_fn(|i| (i / 4 + color_start) as u8);
     let honors: [u8; 7 * 4] = std::array::from_fn(|i| (i / 4 + 27) as u8);
     let mut combined = suits.into_iter().chain(honors);
     let mut wall: [u8; 36 + 28] = std::array::from_fn(|_| combined.next().unwrap());
     wall.shuffle(rng);
-    draw_tiles(wall.as_slice())
+    draw_tiles(wall.as_slice(), size)
 }
 
-fn generate_full_flush_hand(rng: &mut impl Rng) -> [u8; MAX_HAND_SIZE] {
+fn generate_full_flush_hand(rng: &mut impl Rng, size: usize) -> Vec<u8> {
     let color_start = [0, 9, 18].choose(rng).unwrap();
     let mut wall: [u8; 9 * 4] = std::array::from_fn(|i| (i / 4 + color_start) as u8);
     wall.shuffle(rng);
-    draw_tiles(wall.as_slice())
+    draw_tiles(wall.as_slice(), size)
 }
 
-fn generate_non_simple_hand(rng: &mut impl Rng) -> [u8; MAX_HAND_SIZE] {
-    const NON_SIMPLES: [u8; 13] = [0, 8, 9, 17, 18, 26, 27, 28, 29, 30, 31, 32, 33];
-    let mut wall: [u8; 13 * 4] = std::array::from_fn(|i| NON_SIMPLES[i % 13]);
+fn generate_non_simple_hand(rng: &mut impl Rng, size: usize) -> Vec<u8> {
+    let mut wall: [u8; 13 * 4] = std::array::from_fn(|i| TERMINAL_AND_HONOR_TILES[i % 13] as u8);
     wall.shuffle(rng);
-    draw_tiles(wall.as_slice())
+    draw_tiles(wall.as_slice(), size)
+}
+
+fn generate_sanma_hand(rng: &mut impl Rng, size: usize) -> Vec<u8> {
+    let mut wall: Vec<u8> = (0..NUM_TILE_TYPE)
+        .filter(|&i| TileSet::Sanma.contains(i))
+        .flat_map(|i| [i as u8; MAX_NUM_TILE])
+        .collect();
+    debug_assert_eq!(wall.len(), NUM_SANMA_WALL);
+    wall.shuffle(rng);
+    draw_tiles(wall.as_slice(), size)
+}
+
+fn generate_hand(category: Category, rng: &mut impl Rng, size: usize) -> Vec<u8> {
+    match category {
+        Category::Normal => generate_normal_hand(rng, size),
+        Category::HalfFlush => generate_half_flush_hand(rng, size),
+        Category::FullFlush => generate_full_flush_hand(rng, size),
+        Category::ThirteenOrphans => generate_non_simple_hand(rng, size),
+        Category::Sanma => generate_sanma_hand(rng, size),
+        Category::SingleSuit | Category::Game => {
+            unreachable!("{category:?} hands are not drawn from a wall")
+        }
+    }
+}
+
+fn to_tile_counts(tiles: &[u8]) -> TileCounts {
+    let mut hand = [0; NUM_TILE_TYPE];
+    for &tile in tiles {
+        hand[tile as usize] += 1;
+    }
+    hand
+}
+
+/// Draws hands from `generate` until the reference calculator gives the target shanten number.
+fn sample_with_shanten(
+    calculator: &Table,
+    shanten: i8,
+    mut generate: impl FnMut() -> Vec<u8>,
+) -> std::io::Result<Vec<u8>> {
+    for _ in 0..MAX_ATTEMPTS {
+        let tiles = generate();
+        if calculator.calculate_shanten(&to_tile_counts(&tiles)) == shanten {
+            return Ok(tiles);
+        }
+    }
+    Err(std::io::Error::other(format!(
+        "no hand with shanten number {shanten} found in {MAX_ATTEMPTS} attempts"
+    )))
 }
 
-fn write_cases(filename: &str, cases: &Vec<[u8; MAX_HAND_SIZE]>) -> std::io::Result<()> {
+fn write_cases(filename: &Path, cases: &Vec<Vec<u8>>) -> std::io::Result<()> {
     let file = File::create(filename)?;
     let mut writer = BufWriter::new(file);
     for hand in cases {
@@ -59,28 +262,203 @@ fn write_cases(filename: &str, cases: &Vec<[u8; MAX_HAND_SIZE]>) -> std::io::Res
     Ok(())
 }
 
-fn main() -> std::io::Result<()> {
-    let mut rng = StdRng::seed_from_u64(42);
+/// Writes each hand followed by `|` and its general form, Seven Pairs and Thirteen Orphans
+/// shanten numbers, calculated by [Table], [SevenPairs] and [ThirteenOrphans].
+fn write_annotated_cases(filename: &Path, cases: &Vec<Vec<u8>>) -> std::io::Result<()> {
+    let general = Table::new();
+    let seven_pairs = SevenPairs::new();
+    let thirteen_orphans = ThirteenOrphans::new();
+
+    let file = File::create(filename)?;
+    let mut writer = BufWriter::new(file);
+    for hand in cases {
+        for &tile in hand {
+            write!(writer, "{tile} ")?;
+        }
+        let counts = to_tile_counts(hand);
+        writeln!(
+            writer,
+            "| {} {} {}",
+            general.calculate_shanten(&counts),
+            seven_pairs.calculate_shanten(&counts),
+            thirteen_orphans.calculate_shanten(&counts)
+        )?;
+    }
+    Ok(())
+}
+
+fn write_single_suit_cases(filename: &Path, cases: &Vec<(SuitCounts, i8)>) -> std::io::Result<()> {
+    let file = File::create(filename)?;
+    let mut writer = BufWriter::new(file);
+    for (hand, shanten) in cases {
+        let mut counts = [0; NUM_TILE_TYPE];
+        counts[..hand.len()].copy_from_slice(hand);
+        writeln!(writer, "{} {shanten}", to_code(&counts))?;
+    }
+    Ok(())
+}
+
+fn generate_category(args: &Args, category: Category) -> std::io::Result<()> {
+    let path = args.output.clone().unwrap_or_else(|| {
+        default_path(category, args.size, args.count, args.shanten, args.annotate)
+    });
+
+    if category == Category::SingleSuit {
+        return write_single_suit_cases(&path, &generate_single_suit_hands());
+    }
+
+    let cases = match args.shanten {
+        Some(shanten) => generate_stratified_cases(args, category, shanten, args.count)?,
+        None => generate_cases(args, category),
+    };
+
+    if args.annotate {
+        write_annotated_cases(&path, &cases)
+    } else {
+        write_cases(&path, &cases)
+    }
+}
+
+/// Returns the generator of a category.
+///
+/// Each category has its own generator seeded from `seed` and the category,
+/// except the wall categories of [generate_all], which share one generator to keep
+/// the original benchmark hands.
+fn category_rng(seed: u64, category: Category) -> StdRng {
+    StdRng::seed_from_u64(seed ^ ((category as u64) << 32))
+}
+
+/// Generates the hands of a category drawn from a wall or by self-play.
+fn generate_cases(args: &Args, category: Category) -> Vec<Vec<u8>> {
+    let mut rng = category_rng(args.seed, category);
+    if category == Category::Game {
+        let calculator = Table::new();
+        (0..args.count)
+            .map(|_| generate_game_hand(&calculator, &mut rng, &args.turns, args.size))
+            .collect()
+    } else {
+        (0..args.count)
+            .map(|_| generate_hand(category, &mut rng, args.size))
+            .collect()
+    }
+}
 
-    let mut normal_cases = Vec::with_capacity(NUM_CASES);
-    let mut honitsu_cases = Vec::with_capacity(NUM_CASES);
-    let mut chinitsu_cases = Vec::with_capacity(NUM_CASES);
-    let mut non_simple_cases = Vec::with_capacity(NUM_CASES);
+/// Generates hands of a category with the given shanten number by rejection sampling,
+/// using [Table] as the reference calculator.
+fn generate_stratified_cases(
+    args: &Args,
+    category: Category,
+    shanten: i8,
+    count: usize,
+) -> std::io::Result<Vec<Vec<u8>>> {
+    let calculator = Table::new();
+    let mut rng = category_rng(args.seed, category);
+    (0..count)
+        .map(|_| {
+            sample_with_shanten(&calculator, shanten, || match category {
+                Category::Game => generate_game_hand(&calculator, &mut rng, &args.turns, args.size),
+                _ => generate_hand(category, &mut rng, args.size),
+            })
+        })
+        .collect()
+}
+
+fn generate_all(args: &Args) -> std::io::Result<()> {
+    // The hands of these categories are drawn in turn from a single generator,
+    // which reproduces the original benchmark hands
+    let categories = [
+        Category::Normal,
+        Category::HalfFlush,
+        Category::FullFlush,
+        Category::ThirteenOrphans,
+    ];
+    let mut rng = StdRng::seed_from_u64(args.seed);
+    let mut cases = vec![Vec::with_capacity(args.count); categories.len()];
+    for _ in 0..args.count {
+        for (&category, cases) in categories.iter().zip(&mut cases) {
+            cases.push(generate_hand(category, &mut rng, args.size));
+        }
+    }
 
-    for _ in 0..NUM_CASES {
-        normal_cases.push(generate_normal_hand(&mut rng));
-        honitsu_cases.push(generate_half_flush_hand(&mut rng));
-        chinitsu_cases.push(generate_full_flush_hand(&mut rng));
-        non_simple_cases.push(generate_non_simple_hand(&mut rng));
+    for (&category, cases) in categories.iter().zip(&cases) {
+        write_cases(
+            &default_path(category, args.size, args.count, None, false),
+            cases,
+        )?;
+        // The same hands with their shanten numbers, for tests
+        write_annotated_cases(
+            &default_path(category, args.size, args.count, None, true),
+            cases,
+        )?;
     }
 
-    write_cases("resources/hands_normal_10000.txt", &normal_cases)?;
-    write_cases("resources/hands_half_flush_10000.txt", &honitsu_cases)?;
-    write_cases("resources/hands_full_flush_10000.txt", &chinitsu_cases)?;
     write_cases(
-        "resources/hands_thirteen_orphans_10000.txt",
-        &non_simple_cases,
+        &default_path(Category::Sanma, args.size, args.count, None, false),
+        &generate_cases(args, Category::Sanma),
     )?;
 
+    write_single_suit_cases(Path::new(SINGLE_SUIT_PATH), &generate_single_suit_hands())?;
+
+    // Game hands only have 13 or 14 tiles
+    if args.size >= MAX_HAND_SIZE - 1 {
+        write_cases(
+            &default_path(Category::Game, args.size, args.count, None, false),
+            &generate_cases(args, Category::Game),
+        )?;
+
+        // Random hands are rarely tenpai, so hands at or below tenpai are taken from games.
+        // They are also rare in games, so each shanten number has a tenth of the cases.
+        let count = args.count / 10;
+        for shanten in STRATIFIED_SHANTENS.filter(|s| shanten_range(args.size).contains(s)) {
+            let category = if shanten <= 0 {
+                Category::Game
+            } else {
+                Category::Normal
+            };
+            write_cases(
+                &stratified_path(shanten, args.size, count),
+                &generate_stratified_cases(args, category, shanten, count)?,
+            )?;
+        }
+    }
+
     Ok(())
 }
+
+fn main() -> std::io::Result<()> {
+    let args = Args::parse();
+    if args.category == Some(Category::Game) && args.size < MAX_HAND_SIZE - 1 {
+        Args::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                "game hands must have 13 or 14 tiles",
+            )
+            .exit();
+    }
+    if args.category == Some(Category::SingleSuit) && (args.shanten.is_some() || args.annotate) {
+        Args::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                "single-suit hands cannot be filtered by shanten number or annotated",
+            )
+            .exit();
+    }
+    if let Some(shanten) = args.shanten
+        && !shanten_range(args.size).contains(&shanten)
+    {
+        Args::command()
+            .error(
+                ErrorKind::ArgumentConflict,
+                format!(
+                    "no hand of {} tiles has shanten number {shanten}",
+                    args.size
+                ),
+            )
+            .exit();
+    }
+
+    match args.category {
+        Some(category) => generate_category(&args, category),
+        None => generate_all(&args),
+    }
+}
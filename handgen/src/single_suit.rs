@@ -0,0 +1,128 @@
+use common::{MAX_HAND_SIZE, MAX_NUM_TILE};
+
+const NUM_SUIT_TILE: usize = 9;
+const NUM_MAX_MELD: usize = 4;
+/// Number of tile count patterns of a suit (5^9)
+const NUM_PATTERN: usize = 1_953_125;
+/// Blocks of a winning hand: triplets (0-8), sequences (9-15) and a block outside the suit (16)
+const NUM_BLOCK: usize = 17;
+
+/// Tile counts of a single suit.
+pub type SuitCounts = [u8; NUM_SUIT_TILE];
+
+const fn weight(i: usize) -> usize {
+    5usize.pow(i as u32)
+}
+
+fn encode(counts: &SuitCounts) -> usize {
+    counts
+        .iter()
+        .enumerate()
+        .map(|(i, &c)| c as usize * weight(i))
+        .sum()
+}
+
+fn decode(mut index: usize) -> SuitCounts {
+    std::array::from_fn(|_| {
+        let c = index % 5;
+        index /= 5;
+        c as u8
+    })
+}
+
+/// Marks the tiles in the suit of every winning hand with `num_meld` melds and a pair.
+fn mark_winning_hands(
+    target: &mut SuitCounts,
+    num_meld: usize,
+    start: usize,
+    is_winning: &mut [bool],
+) {
+    if num_meld == 0 {
+        if target.iter().all(|&c| c as usize <= MAX_NUM_TILE) {
+            is_winning[encode(target)] = true;
+        }
+        return;
+    }
+
+    for block in start..NUM_BLOCK {
+        match block {
+            0..9 => target[block] += 3,
+            9..16 => (block - 9..block - 6).for_each(|i| target[i] += 1),
+            _ => (),
+        }
+        mark_winning_hands(target, num_meld - 1, block, is_winning);
+        match block {
+            0..9 => target[block] -= 3,
+            9..16 => (block - 9..block - 6).for_each(|i| target[i] -= 1),
+            _ => (),
+        }
+    }
+}
+
+/// Calculates the maximum number of tiles of each hand that can be kept
+/// in a winning hand with `num_meld` melds and a pair.
+fn calculate_max_kept_tiles(num_meld: usize) -> Vec<u8> {
+    // Winning hands (only the tiles in the suit)
+    let mut is_subset = vec![false; NUM_PATTERN];
+    let mut target = [0; NUM_SUIT_TILE];
+    for pair in 0..=NUM_SUIT_TILE {
+        // `NUM_SUIT_TILE` means a pair outside the suit
+        if pair < NUM_SUIT_TILE {
+            target[pair] += 2;
+        }
+        mark_winning_hands(&mut target, num_meld, 0, &mut is_subset);
+        if pair < NUM_SUIT_TILE {
+            target[pair] -= 2;
+        }
+    }
+
+    // Subsets of the winning hands
+    for index in (0..NUM_PATTERN).rev() {
+        let counts = decode(index);
+        is_subset[index] = is_subset[index]
+            || (0..NUM_SUIT_TILE)
+                .any(|i| (counts[i] as usize) < MAX_NUM_TILE && is_subset[index + weight(i)]);
+    }
+
+    // The largest subset of a winning hand contained in each hand
+    let mut max_kept = vec![0u8; NUM_PATTERN];
+    for index in 0..NUM_PATTERN {
+        let counts = decode(index);
+        max_kept[index] = if is_subset[index] {
+            counts.iter().sum()
+        } else {
+            (0..NUM_SUIT_TILE)
+                .filter(|&i| counts[i] > 0)
+                .map(|i| max_kept[index - weight(i)])
+                .max()
+                .unwrap()
+        };
+    }
+
+    max_kept
+}
+
+/// Enumerates every single-suit hand of 3n+1 or 3n+2 tiles with its shanten number.
+///
+/// The shanten number is calculated by definition: the minimum number of tiles
+/// to exchange to reach a winning hand, minus 1.
+/// Tiles of the winning hand outside the suit can never be kept.
+pub fn generate_single_suit_hands() -> Vec<(SuitCounts, i8)> {
+    let max_kept: Vec<_> = (0..=NUM_MAX_MELD).map(calculate_max_kept_tiles).collect();
+    let mut cases = Vec::new();
+
+    for hand in (0..NUM_PATTERN).map(decode) {
+        let num_tiles = hand.iter().sum::<u8>() as usize;
+        if num_tiles > MAX_HAND_SIZE || num_tiles.is_multiple_of(3) {
+            continue;
+        }
+
+        let num_meld = num_tiles / 3;
+        let num_winning_tiles = num_meld * 3 + 2;
+        let num_exchange = num_winning_tiles - max_kept[num_meld][encode(&hand)] as usize;
+        cases.push((hand, num_exchange as i8 - 1));
+    }
+
+    cases.sort_by_key(|&(hand, _)| (hand.iter().sum::<u8>(), std::cmp::Reverse(hand)));
+    cases
+}